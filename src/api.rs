@@ -0,0 +1,615 @@
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use futures_util::stream::BoxStream;
+
+use crate::client::Client;
+use crate::error::Error;
+use crate::pagination::paginate;
+use crate::txid::Txid;
+use crate::types::{
+    BillingChargeCreateRequest, BillingChargeDetailResponse, BillingChargeOneStepRequest,
+    BillingChargePayRequest, BillingChargeResponse, BillingNotificationResponse,
+    CarnetCreateRequest, CarnetHistoryRequest, CarnetMetadataRequest, CarnetParcelRequest,
+    CarnetParcelsRequest, CarnetResponse, CobListQuery, CobListResponse, CobPayload, CobResponse,
+    CobvListResponse, CobvPayload, CobvResponse, Devolucao, DevolucaoPayload, PixEnviadoListQuery,
+    PixEnviadoListResponse, PixRecebido, PixRecebidoListQuery, PixRecebidoListResponse,
+    PixTransactionDetailResponse, PixTransactionPayload, PixTransactionResponse,
+    SplitConfigResponse, SplitLinkResponse, SplitPayload, WebhookPayload, WebhookResponse,
+    WebhooksListResponse,
+};
+
+#[async_trait]
+pub trait PixChargesApi: Send + Sync {
+    async fn cob_create(&self, payload: &CobPayload) -> Result<CobResponse, Error>;
+
+    async fn cob_create_with_txid(
+        &self,
+        txid: &Txid,
+        payload: &CobPayload,
+    ) -> Result<CobResponse, Error>;
+
+    async fn cob_update(&self, txid: &str, payload: &CobPayload) -> Result<CobResponse, Error>;
+
+    async fn cob_patch(&self, txid: &str, payload: &CobPayload) -> Result<CobResponse, Error>;
+
+    async fn cob_get(&self, txid: &str) -> Result<CobResponse, Error>;
+
+    async fn cob_list(&self, query: &CobListQuery) -> Result<CobListResponse, Error>;
+
+    /// Every charge matching `query`, fetching one page at a time as the
+    /// stream is polled. Starts at `query.pagina_atual`, or the first page.
+    fn cob_list_stream(&self, query: CobListQuery) -> BoxStream<'_, Result<CobResponse, Error>> {
+        paginate(query.pagina_atual.unwrap_or(0), move |page| {
+            let query = query.clone().pagina_atual(page);
+            async move {
+                let response = self.cob_list(&query).await?;
+                Ok((response.cobs, response.parametros.paginacao))
+            }
+        })
+        .boxed()
+    }
+
+    async fn cobv_create(&self, payload: &CobvPayload) -> Result<CobvResponse, Error>;
+
+    async fn cobv_update(&self, txid: &str, payload: &CobvPayload) -> Result<CobvResponse, Error>;
+
+    async fn cobv_patch(&self, txid: &str, payload: &CobvPayload) -> Result<CobvResponse, Error>;
+
+    async fn cobv_get(&self, txid: &str) -> Result<CobvResponse, Error>;
+
+    async fn cobv_list(&self, query: &CobListQuery) -> Result<CobvListResponse, Error>;
+
+    /// Every charge matching `query`, fetching one page at a time as the
+    /// stream is polled. Starts at `query.pagina_atual`, or the first page.
+    fn cobv_list_stream(&self, query: CobListQuery) -> BoxStream<'_, Result<CobvResponse, Error>> {
+        paginate(query.pagina_atual.unwrap_or(0), move |page| {
+            let query = query.clone().pagina_atual(page);
+            async move {
+                let response = self.cobv_list(&query).await?;
+                Ok((response.cobs, response.parametros.paginacao))
+            }
+        })
+        .boxed()
+    }
+}
+
+#[async_trait]
+pub trait PixTransfersApi: Send + Sync {
+    async fn pix_send(
+        &self,
+        payload: &PixTransactionPayload,
+    ) -> Result<PixTransactionResponse, Error>;
+
+    async fn pix_get_transaction(
+        &self,
+        end_to_end_id: &str,
+    ) -> Result<PixTransactionDetailResponse, Error>;
+
+    async fn pix_get_transaction_by_id_envio(
+        &self,
+        id_envio: &str,
+    ) -> Result<PixTransactionDetailResponse, Error>;
+
+    async fn pix_sent_list(
+        &self,
+        query: &PixEnviadoListQuery,
+    ) -> Result<PixEnviadoListResponse, Error>;
+
+    /// Every sent Pix matching `query`, fetching one page at a time as the
+    /// stream is polled. Starts at `query.pagina_atual`, or the first page.
+    fn pix_sent_list_stream(
+        &self,
+        query: PixEnviadoListQuery,
+    ) -> BoxStream<'_, Result<PixTransactionDetailResponse, Error>> {
+        paginate(query.pagina_atual.unwrap_or(0), move |page| {
+            let query = query.clone().pagina_atual(page);
+            async move {
+                let response = self.pix_sent_list(&query).await?;
+                Ok((response.pix, response.parametros.paginacao))
+            }
+        })
+        .boxed()
+    }
+
+    async fn pix_received_list(
+        &self,
+        query: &PixRecebidoListQuery,
+    ) -> Result<PixRecebidoListResponse, Error>;
+
+    /// Every Pix matching `query`, fetching one page at a time as the stream
+    /// is polled. Starts at `query.pagina_atual`, or the first page.
+    fn pix_received_list_stream(
+        &self,
+        query: PixRecebidoListQuery,
+    ) -> BoxStream<'_, Result<PixRecebido, Error>> {
+        paginate(query.pagina_atual.unwrap_or(0), move |page| {
+            let query = query.clone().pagina_atual(page);
+            async move {
+                let response = self.pix_received_list(&query).await?;
+                Ok((response.pix, response.parametros.paginacao))
+            }
+        })
+        .boxed()
+    }
+
+    async fn pix_received_get(&self, end_to_end_id: &str) -> Result<PixRecebido, Error>;
+
+    async fn pix_refund_create(
+        &self,
+        end_to_end_id: &str,
+        refund_id: &str,
+        payload: &DevolucaoPayload,
+    ) -> Result<Devolucao, Error>;
+
+    async fn pix_refund_get(
+        &self,
+        end_to_end_id: &str,
+        refund_id: &str,
+    ) -> Result<Devolucao, Error>;
+}
+
+#[async_trait]
+pub trait WebhooksApi: Send + Sync {
+    async fn webhook_create(&self, payload: &WebhookPayload) -> Result<WebhookResponse, Error>;
+
+    async fn webhook_update(
+        &self,
+        webhook_id: &str,
+        payload: &WebhookPayload,
+    ) -> Result<WebhookResponse, Error>;
+
+    async fn webhook_get(&self, webhook_id: &str) -> Result<WebhookResponse, Error>;
+
+    async fn webhook_list(&self) -> Result<WebhooksListResponse, Error>;
+
+    async fn webhook_delete(&self, webhook_id: &str) -> Result<(), Error>;
+}
+
+#[async_trait]
+pub trait SplitApi: Send + Sync {
+    async fn split_create_config(
+        &self,
+        payload: &SplitPayload,
+    ) -> Result<SplitConfigResponse, Error>;
+
+    async fn split_update_config(
+        &self,
+        config_id: &str,
+        payload: &SplitPayload,
+    ) -> Result<SplitConfigResponse, Error>;
+
+    async fn split_get_config(&self, config_id: &str) -> Result<SplitConfigResponse, Error>;
+
+    async fn split_link_cob(
+        &self,
+        txid: &str,
+        split_config_id: &str,
+    ) -> Result<SplitLinkResponse, Error>;
+
+    async fn split_get_cob(&self, txid: &str) -> Result<SplitConfigResponse, Error>;
+
+    async fn split_unlink_cob(&self, txid: &str) -> Result<SplitLinkResponse, Error>;
+
+    async fn split_link_cobv(
+        &self,
+        txid: &str,
+        split_config_id: &str,
+    ) -> Result<SplitLinkResponse, Error>;
+
+    async fn split_get_cobv(&self, txid: &str) -> Result<SplitConfigResponse, Error>;
+
+    async fn split_unlink_cobv(&self, txid: &str) -> Result<SplitLinkResponse, Error>;
+}
+
+#[async_trait]
+pub trait BillingApi: Send + Sync {
+    async fn billing_charge_create(
+        &self,
+        payload: &BillingChargeCreateRequest,
+    ) -> Result<BillingChargeResponse, Error>;
+
+    async fn billing_charge_one_step(
+        &self,
+        payload: &BillingChargeOneStepRequest,
+    ) -> Result<BillingChargeResponse, Error>;
+
+    async fn billing_charge_pay(
+        &self,
+        charge_id: i64,
+        payload: &BillingChargePayRequest,
+    ) -> Result<BillingChargeResponse, Error>;
+
+    async fn billing_charge_get(
+        &self,
+        charge_id: i64,
+    ) -> Result<BillingChargeDetailResponse, Error>;
+
+    async fn billing_notification_get(
+        &self,
+        token: &str,
+    ) -> Result<BillingNotificationResponse, Error>;
+}
+
+#[async_trait]
+pub trait CarnetApi: Send + Sync {
+    async fn carnet_create(&self, payload: &CarnetCreateRequest) -> Result<CarnetResponse, Error>;
+
+    async fn carnet_get(&self, carnet_id: i64) -> Result<CarnetResponse, Error>;
+
+    async fn carnet_list(&self, begin_date: &str, end_date: &str) -> Result<CarnetResponse, Error>;
+
+    async fn carnet_update_metadata(
+        &self,
+        carnet_id: i64,
+        payload: &CarnetMetadataRequest,
+    ) -> Result<CarnetResponse, Error>;
+
+    async fn carnet_update_parcel(
+        &self,
+        carnet_id: i64,
+        parcel: i32,
+        payload: &CarnetParcelRequest,
+    ) -> Result<CarnetResponse, Error>;
+
+    async fn carnet_update_parcels(
+        &self,
+        carnet_id: i64,
+        payload: &CarnetParcelsRequest,
+    ) -> Result<CarnetResponse, Error>;
+
+    async fn carnet_cancel(&self, carnet_id: i64) -> Result<CarnetResponse, Error>;
+
+    async fn carnet_cancel_parcel(
+        &self,
+        carnet_id: i64,
+        parcel: i32,
+    ) -> Result<CarnetResponse, Error>;
+
+    async fn carnet_resend(&self, carnet_id: i64) -> Result<CarnetResponse, Error>;
+
+    async fn carnet_resend_parcel(
+        &self,
+        carnet_id: i64,
+        parcel: i32,
+    ) -> Result<CarnetResponse, Error>;
+
+    async fn carnet_add_history(
+        &self,
+        carnet_id: i64,
+        payload: &CarnetHistoryRequest,
+    ) -> Result<CarnetResponse, Error>;
+
+    async fn carnet_settle(&self, carnet_id: i64) -> Result<CarnetResponse, Error>;
+
+    async fn carnet_settle_parcel(
+        &self,
+        carnet_id: i64,
+        parcel: i32,
+    ) -> Result<CarnetResponse, Error>;
+}
+
+/// Every Efí operation, grouped by API area. Depend on `dyn EfiApi` (or one of the
+/// area traits) to swap [`Client`] for [`FakeEfi`](crate::fake::FakeEfi) in tests.
+pub trait EfiApi:
+    PixChargesApi + PixTransfersApi + WebhooksApi + SplitApi + BillingApi + CarnetApi
+{
+}
+
+impl<T> EfiApi for T where
+    T: PixChargesApi + PixTransfersApi + WebhooksApi + SplitApi + BillingApi + CarnetApi
+{
+}
+
+#[async_trait]
+impl PixChargesApi for Client {
+    async fn cob_create(&self, payload: &CobPayload) -> Result<CobResponse, Error> {
+        Self::cob_create(self, payload).await
+    }
+
+    async fn cob_create_with_txid(
+        &self,
+        txid: &Txid,
+        payload: &CobPayload,
+    ) -> Result<CobResponse, Error> {
+        Self::cob_create_with_txid(self, txid, payload).await
+    }
+
+    async fn cob_update(&self, txid: &str, payload: &CobPayload) -> Result<CobResponse, Error> {
+        Self::cob_update(self, txid, payload).await
+    }
+
+    async fn cob_patch(&self, txid: &str, payload: &CobPayload) -> Result<CobResponse, Error> {
+        Self::cob_patch(self, txid, payload).await
+    }
+
+    async fn cob_get(&self, txid: &str) -> Result<CobResponse, Error> {
+        Self::cob_get(self, txid).await
+    }
+
+    async fn cob_list(&self, query: &CobListQuery) -> Result<CobListResponse, Error> {
+        Self::cob_list(self, query).await
+    }
+
+    async fn cobv_create(&self, payload: &CobvPayload) -> Result<CobvResponse, Error> {
+        Self::cobv_create(self, payload).await
+    }
+
+    async fn cobv_update(&self, txid: &str, payload: &CobvPayload) -> Result<CobvResponse, Error> {
+        Self::cobv_update(self, txid, payload).await
+    }
+
+    async fn cobv_patch(&self, txid: &str, payload: &CobvPayload) -> Result<CobvResponse, Error> {
+        Self::cobv_patch(self, txid, payload).await
+    }
+
+    async fn cobv_get(&self, txid: &str) -> Result<CobvResponse, Error> {
+        Self::cobv_get(self, txid).await
+    }
+
+    async fn cobv_list(&self, query: &CobListQuery) -> Result<CobvListResponse, Error> {
+        Self::cobv_list(self, query).await
+    }
+}
+
+#[async_trait]
+impl PixTransfersApi for Client {
+    async fn pix_send(
+        &self,
+        payload: &PixTransactionPayload,
+    ) -> Result<PixTransactionResponse, Error> {
+        Self::pix_send(self, payload).await
+    }
+
+    async fn pix_get_transaction(
+        &self,
+        end_to_end_id: &str,
+    ) -> Result<PixTransactionDetailResponse, Error> {
+        Self::pix_get_transaction(self, end_to_end_id).await
+    }
+
+    async fn pix_get_transaction_by_id_envio(
+        &self,
+        id_envio: &str,
+    ) -> Result<PixTransactionDetailResponse, Error> {
+        Self::pix_get_transaction_by_id_envio(self, id_envio).await
+    }
+
+    async fn pix_sent_list(
+        &self,
+        query: &PixEnviadoListQuery,
+    ) -> Result<PixEnviadoListResponse, Error> {
+        Self::pix_sent_list(self, query).await
+    }
+
+    async fn pix_received_list(
+        &self,
+        query: &PixRecebidoListQuery,
+    ) -> Result<PixRecebidoListResponse, Error> {
+        Self::pix_received_list(self, query).await
+    }
+
+    async fn pix_received_get(&self, end_to_end_id: &str) -> Result<PixRecebido, Error> {
+        Self::pix_received_get(self, end_to_end_id).await
+    }
+
+    async fn pix_refund_create(
+        &self,
+        end_to_end_id: &str,
+        refund_id: &str,
+        payload: &DevolucaoPayload,
+    ) -> Result<Devolucao, Error> {
+        Self::pix_refund_create(self, end_to_end_id, refund_id, payload).await
+    }
+
+    async fn pix_refund_get(
+        &self,
+        end_to_end_id: &str,
+        refund_id: &str,
+    ) -> Result<Devolucao, Error> {
+        Self::pix_refund_get(self, end_to_end_id, refund_id).await
+    }
+}
+
+#[async_trait]
+impl WebhooksApi for Client {
+    async fn webhook_create(&self, payload: &WebhookPayload) -> Result<WebhookResponse, Error> {
+        Self::webhook_create(self, payload).await
+    }
+
+    async fn webhook_update(
+        &self,
+        webhook_id: &str,
+        payload: &WebhookPayload,
+    ) -> Result<WebhookResponse, Error> {
+        Self::webhook_update(self, webhook_id, payload).await
+    }
+
+    async fn webhook_get(&self, webhook_id: &str) -> Result<WebhookResponse, Error> {
+        Self::webhook_get(self, webhook_id).await
+    }
+
+    async fn webhook_list(&self) -> Result<WebhooksListResponse, Error> {
+        Self::webhook_list(self).await
+    }
+
+    async fn webhook_delete(&self, webhook_id: &str) -> Result<(), Error> {
+        Self::webhook_delete(self, webhook_id).await
+    }
+}
+
+#[async_trait]
+impl SplitApi for Client {
+    async fn split_create_config(
+        &self,
+        payload: &SplitPayload,
+    ) -> Result<SplitConfigResponse, Error> {
+        Self::split_create_config(self, payload).await
+    }
+
+    async fn split_update_config(
+        &self,
+        config_id: &str,
+        payload: &SplitPayload,
+    ) -> Result<SplitConfigResponse, Error> {
+        Self::split_update_config(self, config_id, payload).await
+    }
+
+    async fn split_get_config(&self, config_id: &str) -> Result<SplitConfigResponse, Error> {
+        Self::split_get_config(self, config_id).await
+    }
+
+    async fn split_link_cob(
+        &self,
+        txid: &str,
+        split_config_id: &str,
+    ) -> Result<SplitLinkResponse, Error> {
+        Self::split_link_cob(self, txid, split_config_id).await
+    }
+
+    async fn split_get_cob(&self, txid: &str) -> Result<SplitConfigResponse, Error> {
+        Self::split_get_cob(self, txid).await
+    }
+
+    async fn split_unlink_cob(&self, txid: &str) -> Result<SplitLinkResponse, Error> {
+        Self::split_unlink_cob(self, txid).await
+    }
+
+    async fn split_link_cobv(
+        &self,
+        txid: &str,
+        split_config_id: &str,
+    ) -> Result<SplitLinkResponse, Error> {
+        Self::split_link_cobv(self, txid, split_config_id).await
+    }
+
+    async fn split_get_cobv(&self, txid: &str) -> Result<SplitConfigResponse, Error> {
+        Self::split_get_cobv(self, txid).await
+    }
+
+    async fn split_unlink_cobv(&self, txid: &str) -> Result<SplitLinkResponse, Error> {
+        Self::split_unlink_cobv(self, txid).await
+    }
+}
+
+#[async_trait]
+impl BillingApi for Client {
+    async fn billing_charge_create(
+        &self,
+        payload: &BillingChargeCreateRequest,
+    ) -> Result<BillingChargeResponse, Error> {
+        Self::billing_charge_create(self, payload).await
+    }
+
+    async fn billing_charge_one_step(
+        &self,
+        payload: &BillingChargeOneStepRequest,
+    ) -> Result<BillingChargeResponse, Error> {
+        Self::billing_charge_one_step(self, payload).await
+    }
+
+    async fn billing_charge_pay(
+        &self,
+        charge_id: i64,
+        payload: &BillingChargePayRequest,
+    ) -> Result<BillingChargeResponse, Error> {
+        Self::billing_charge_pay(self, charge_id, payload).await
+    }
+
+    async fn billing_charge_get(
+        &self,
+        charge_id: i64,
+    ) -> Result<BillingChargeDetailResponse, Error> {
+        Self::billing_charge_get(self, charge_id).await
+    }
+
+    async fn billing_notification_get(
+        &self,
+        token: &str,
+    ) -> Result<BillingNotificationResponse, Error> {
+        Self::billing_notification_get(self, token).await
+    }
+}
+
+#[async_trait]
+impl CarnetApi for Client {
+    async fn carnet_create(&self, payload: &CarnetCreateRequest) -> Result<CarnetResponse, Error> {
+        Self::carnet_create(self, payload).await
+    }
+
+    async fn carnet_get(&self, carnet_id: i64) -> Result<CarnetResponse, Error> {
+        Self::carnet_get(self, carnet_id).await
+    }
+
+    async fn carnet_list(&self, begin_date: &str, end_date: &str) -> Result<CarnetResponse, Error> {
+        Self::carnet_list(self, begin_date, end_date).await
+    }
+
+    async fn carnet_update_metadata(
+        &self,
+        carnet_id: i64,
+        payload: &CarnetMetadataRequest,
+    ) -> Result<CarnetResponse, Error> {
+        Self::carnet_update_metadata(self, carnet_id, payload).await
+    }
+
+    async fn carnet_update_parcel(
+        &self,
+        carnet_id: i64,
+        parcel: i32,
+        payload: &CarnetParcelRequest,
+    ) -> Result<CarnetResponse, Error> {
+        Self::carnet_update_parcel(self, carnet_id, parcel, payload).await
+    }
+
+    async fn carnet_update_parcels(
+        &self,
+        carnet_id: i64,
+        payload: &CarnetParcelsRequest,
+    ) -> Result<CarnetResponse, Error> {
+        Self::carnet_update_parcels(self, carnet_id, payload).await
+    }
+
+    async fn carnet_cancel(&self, carnet_id: i64) -> Result<CarnetResponse, Error> {
+        Self::carnet_cancel(self, carnet_id).await
+    }
+
+    async fn carnet_cancel_parcel(
+        &self,
+        carnet_id: i64,
+        parcel: i32,
+    ) -> Result<CarnetResponse, Error> {
+        Self::carnet_cancel_parcel(self, carnet_id, parcel).await
+    }
+
+    async fn carnet_resend(&self, carnet_id: i64) -> Result<CarnetResponse, Error> {
+        Self::carnet_resend(self, carnet_id).await
+    }
+
+    async fn carnet_resend_parcel(
+        &self,
+        carnet_id: i64,
+        parcel: i32,
+    ) -> Result<CarnetResponse, Error> {
+        Self::carnet_resend_parcel(self, carnet_id, parcel).await
+    }
+
+    async fn carnet_add_history(
+        &self,
+        carnet_id: i64,
+        payload: &CarnetHistoryRequest,
+    ) -> Result<CarnetResponse, Error> {
+        Self::carnet_add_history(self, carnet_id, payload).await
+    }
+
+    async fn carnet_settle(&self, carnet_id: i64) -> Result<CarnetResponse, Error> {
+        Self::carnet_settle(self, carnet_id).await
+    }
+
+    async fn carnet_settle_parcel(
+        &self,
+        carnet_id: i64,
+        parcel: i32,
+    ) -> Result<CarnetResponse, Error> {
+        Self::carnet_settle_parcel(self, carnet_id, parcel).await
+    }
+}
@@ -1,13 +1,22 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+use futures_util::FutureExt;
+use futures_util::future::{BoxFuture, Shared};
 use serde::Deserialize;
 use serde_json::json;
 
 use crate::client::Client;
+use crate::environment::Api;
 use crate::error::Error;
 
 const TOKEN_REFRESH_SKEW_SECS: u64 = 30;
 
+pub(crate) type TokenStore = Arc<Mutex<HashMap<Api, TokenSlot>>>;
+
+type TokenRefresh = Shared<BoxFuture<'static, Result<String, Arc<Error>>>>;
+
 #[derive(Debug)]
 pub(crate) struct AccessToken {
     pub(crate) value: String,
@@ -21,6 +30,15 @@ impl AccessToken {
     }
 }
 
+/// Cached token for one API plus the refresh currently in flight, if any.
+/// Every caller that finds the token missing or expired awaits the same
+/// `refresh`, so only one OAuth request per API is ever outstanding.
+#[derive(Default)]
+pub(crate) struct TokenSlot {
+    token: Option<AccessToken>,
+    refresh: Option<TokenRefresh>,
+}
+
 #[derive(Debug, Deserialize)]
 struct OAuthResponse {
     access_token: String,
@@ -29,72 +47,196 @@ struct OAuthResponse {
 
 impl Client {
     pub async fn authenticate(&self) -> Result<(), Error> {
-        let endpoints = self.endpoints();
-        self.authenticate_with_url(endpoints.pix_api_oauth_token_url)
-            .await
+        self.authenticate_api(Api::Pix).await
     }
 
     pub async fn authenticate_billing(&self) -> Result<(), Error> {
-        let endpoints = self.endpoints();
-        self.authenticate_with_url(endpoints.billing_api_oauth_token_url)
-            .await
+        self.authenticate_api(Api::Billing).await
     }
 
-    pub(crate) async fn get_valid_access_token(&self) -> Result<String, Error> {
-        let endpoints = self.endpoints();
-        self.get_valid_access_token_with_url(endpoints.pix_api_oauth_token_url)
-            .await
+    /// Forces a new token for `api`, joining a refresh that is already in flight.
+    pub(crate) async fn authenticate_api(&self, api: Api) -> Result<(), Error> {
+        let refresh = {
+            let mut slots = self.tokens.lock().map_err(|_| Error::AuthUnavailable)?;
+            let slot = slots.entry(api).or_default();
+            slot.refresh
+                .get_or_insert_with(|| self.refresh_access_token(api))
+                .clone()
+        };
+
+        refresh.await.map(|_| ()).map_err(Error::AuthFailed)
     }
 
-    pub(crate) async fn get_valid_billing_access_token(&self) -> Result<String, Error> {
-        let endpoints = self.endpoints();
-        self.get_valid_access_token_with_url(endpoints.billing_api_oauth_token_url)
-            .await
+    pub(crate) async fn get_valid_access_token(&self, api: Api) -> Result<String, Error> {
+        let refresh = {
+            let mut slots = self.tokens.lock().map_err(|_| Error::AuthUnavailable)?;
+            let slot = slots.entry(api).or_default();
+
+            if let Some(token) = slot.token.as_ref().filter(|token| !token.is_expired()) {
+                return Ok(token.value.clone());
+            }
+
+            slot.refresh
+                .get_or_insert_with(|| self.refresh_access_token(api))
+                .clone()
+        };
+
+        refresh.await.map_err(Error::AuthFailed)
     }
 
-    async fn authenticate_with_url(&self, token_url: &str) -> Result<(), Error> {
-        let response = self
-            .http
-            .post(token_url)
-            .basic_auth(&self.id, Some(&self.secret))
-            .json(&json!({ "grant_type": "client_credentials" }))
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_else(|_| String::new());
-            return Err(Error::RequestFailed { status, body });
+    /// Drops the cached token for `api` if it is still the one the server rejected,
+    /// so concurrent 401s trigger a single refresh instead of one each.
+    pub(crate) fn invalidate_access_token(&self, api: Api, rejected: &str) -> Result<(), Error> {
+        let mut slots = self.tokens.lock().map_err(|_| Error::AuthUnavailable)?;
+        if let Some(slot) = slots.get_mut(&api)
+            && slot
+                .token
+                .as_ref()
+                .is_some_and(|token| token.value == rejected)
+        {
+            slot.token = None;
         }
+        Ok(())
+    }
 
-        let oauth = response.json::<OAuthResponse>().await?;
-        let expires_at = Instant::now() + Duration::from_secs(oauth.expires_in);
+    fn refresh_access_token(&self, api: Api) -> TokenRefresh {
+        let http = self.http.clone();
+        let token_url = self.endpoints().oauth_token_url(api).to_owned();
+        let client_id = self.id.clone();
+        let client_secret = self.secret.clone();
+        let tokens = Arc::clone(&self.tokens);
+
+        async move {
+            let result =
+                request_access_token(&http, api, &token_url, &client_id, &client_secret).await;
+
+            let mut slots = tokens
+                .lock()
+                .map_err(|_| Arc::new(Error::AuthUnavailable))?;
+            let slot = slots.entry(api).or_default();
+            slot.refresh = None;
+
+            match result {
+                Ok(token) => {
+                    let value = token.value.clone();
+                    slot.token = Some(token);
+                    Ok(value)
+                }
+                Err(error) => {
+                    slot.token = None;
+                    Err(Arc::new(error))
+                }
+            }
+        }
+        .boxed()
+        .shared()
+    }
+}
+
+async fn request_access_token(
+    http: &reqwest::Client,
+    api: Api,
+    token_url: &str,
+    client_id: &str,
+    client_secret: &str,
+) -> Result<AccessToken, Error> {
+    let response = http
+        .post(token_url)
+        .basic_auth(client_id, Some(client_secret))
+        .json(&json!({ "grant_type": "client_credentials" }))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_else(|_| String::new());
+        return Err(Error::from_response(api, status, body));
+    }
 
-        self.token
-            .lock()
-            .map_err(|_| Error::AuthUnavailable)?
-            .replace(AccessToken {
-                value: oauth.access_token,
-                expires_at,
-            });
+    let oauth = response.json::<OAuthResponse>().await?;
+    let expires_at = Instant::now() + Duration::from_secs(oauth.expires_in);
 
-        Ok(())
+    Ok(AccessToken {
+        value: oauth.access_token,
+        expires_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures_util::future::join_all;
+
+    use crate::environment::Api;
+    use crate::error::Error;
+    use crate::retry::RetryPolicy;
+    use crate::test_server::{Reply, TestServer};
+
+    async fn token_server(status: u16) -> TestServer {
+        TestServer::start(move |_, _, hit| {
+            let reply = if status == 200 {
+                Reply::token(&format!("token-{hit}"))
+            } else {
+                Reply::new(status, r#"{"error":"invalid_client"}"#)
+            };
+            reply.delay(Duration::from_millis(50))
+        })
+        .await
     }
 
-    async fn get_valid_access_token_with_url(&self, token_url: &str) -> Result<String, Error> {
-        let needs_authentication = {
-            let token = self.token.lock().map_err(|_| Error::AuthUnavailable)?;
-            token.as_ref().is_none_or(AccessToken::is_expired)
-        };
+    #[tokio::test]
+    async fn concurrent_callers_share_one_token_request() {
+        let server = token_server(200).await;
+        let client = server.client(RetryPolicy::disabled());
 
-        if needs_authentication {
-            self.authenticate_with_url(token_url).await?;
+        let tokens = join_all((0..8).map(|_| client.get_valid_access_token(Api::Pix))).await;
+        for token in tokens {
+            assert_eq!(token.unwrap(), "token-1");
         }
+        assert_eq!(
+            client.get_valid_access_token(Api::Pix).await.unwrap(),
+            "token-1"
+        );
+        assert_eq!(server.hits("/oauth/token"), 1);
+    }
+
+    #[tokio::test]
+    async fn failed_refresh_reaches_every_waiter() {
+        let server = token_server(401).await;
+        let client = server.client(RetryPolicy::disabled());
+
+        let results = join_all((0..4).map(|_| client.get_valid_access_token(Api::Pix))).await;
+        for result in results {
+            assert!(matches!(result, Err(Error::AuthFailed(_))));
+        }
+        assert_eq!(server.hits("/oauth/token"), 1);
+
+        assert!(client.authenticate().await.is_err());
+        assert_eq!(server.hits("/oauth/token"), 2);
+    }
 
-        let token = self.token.lock().map_err(|_| Error::AuthUnavailable)?;
-        token
-            .as_ref()
-            .map(|cached| cached.value.clone())
-            .ok_or(Error::AuthUnavailable)
+    #[tokio::test]
+    async fn invalidate_drops_only_the_rejected_token() {
+        let server = token_server(200).await;
+        let client = server.client(RetryPolicy::disabled());
+        let first = client.get_valid_access_token(Api::Pix).await.unwrap();
+
+        client.invalidate_access_token(Api::Pix, "token-0").unwrap();
+        client
+            .invalidate_access_token(Api::Billing, &first)
+            .unwrap();
+        assert_eq!(
+            client.get_valid_access_token(Api::Pix).await.unwrap(),
+            first
+        );
+        assert_eq!(server.hits("/oauth/token"), 1);
+
+        client.invalidate_access_token(Api::Pix, &first).unwrap();
+        assert_eq!(
+            client.get_valid_access_token(Api::Pix).await.unwrap(),
+            "token-2"
+        );
+        assert_eq!(server.hits("/oauth/token"), 2);
     }
 }
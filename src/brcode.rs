@@ -0,0 +1,624 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::money::Brl;
+use crate::pix_key::PixKey;
+
+const PIX_GUI: &str = "br.gov.bcb.pix";
+const CRC_TAG: &str = "6304";
+
+/// Decoded Pix "copia e cola" payload (EMV QRCPS merchant-presented mode), as
+/// returned in `CobResponse.pix_copia_e_cola`, `CobvResponse.pix_copia_e_cola` and
+/// `BillingPix.qrcode`.
+///
+/// Parsing checks the TLV structure, the mandatory fields and the CRC16.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrCode {
+    pub point_of_initiation: Option<PointOfInitiation>,
+    pub merchant_account: PixMerchantAccount,
+    pub merchant_category_code: String,
+    pub currency: String,
+    pub amount: Option<Brl>,
+    pub country_code: String,
+    pub merchant_name: String,
+    pub merchant_city: String,
+    pub postal_code: Option<String>,
+    /// Reference label (`62.05`). `None` when absent or set to `***`.
+    pub txid: Option<String>,
+    pub crc: u16,
+}
+
+/// Merchant account information template carrying the `br.gov.bcb.pix` GUI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PixMerchantAccount {
+    pub gui: String,
+    /// Pix key, present on static codes.
+    pub key: Option<String>,
+    pub description: Option<String>,
+    /// Payload URL without scheme, present on dynamic codes.
+    pub location: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointOfInitiation {
+    /// `11`: the code may be paid more than once.
+    Static,
+    /// `12`: the code is meant to be paid once.
+    Dynamic,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BrCodeError {
+    /// The TLV structure is broken at byte `offset`.
+    Malformed {
+        offset: usize,
+        reason: &'static str,
+    },
+    MissingField(&'static str),
+    InvalidField {
+        field: &'static str,
+        reason: &'static str,
+    },
+    ChecksumMismatch {
+        expected: u16,
+        actual: u16,
+    },
+}
+
+impl BrCode {
+    pub fn parse(input: &str) -> Result<Self, BrCodeError> {
+        let fields = read_fields(input)?;
+
+        let Some(&(crc_offset, crc_tag, crc)) = fields.last() else {
+            return Err(BrCodeError::MissingField(field_name(0)));
+        };
+        if crc_tag != 63 {
+            return Err(BrCodeError::MissingField(field_name(63)));
+        }
+        let actual = u16::from_str_radix(crc, 16)
+            .ok()
+            .filter(|_| crc.len() == 4 && crc.bytes().all(|b| b.is_ascii_hexdigit()))
+            .ok_or_else(|| invalid(63, "expected four hexadecimal digits"))?;
+        let expected = crc16(&input.as_bytes()[..crc_offset + CRC_TAG.len()]);
+        if expected != actual {
+            return Err(BrCodeError::ChecksumMismatch { expected, actual });
+        }
+
+        if fields.first().map(|&(_, tag, _)| tag) != Some(0) {
+            return Err(BrCodeError::Malformed {
+                offset: 0,
+                reason: "payload format indicator must come first",
+            });
+        }
+        let get = |tag: u8| {
+            fields
+                .iter()
+                .find(|&&(_, field, _)| field == tag)
+                .map(|&(_, _, value)| value)
+        };
+        let require = |tag: u8| get(tag).ok_or(BrCodeError::MissingField(field_name(tag)));
+
+        if require(0)? != "01" {
+            return Err(invalid(0, "expected 01"));
+        }
+        let point_of_initiation = match get(1) {
+            None => None,
+            Some("11") => Some(PointOfInitiation::Static),
+            Some("12") => Some(PointOfInitiation::Dynamic),
+            Some(_) => return Err(invalid(1, "expected 11 or 12")),
+        };
+
+        let mut merchant_account = None;
+        for &(_, tag, value) in &fields {
+            if (26..=51).contains(&tag)
+                && let Some(account) = parse_merchant_account(value)?
+            {
+                merchant_account = Some(account);
+                break;
+            }
+        }
+        let merchant_account = merchant_account.ok_or(BrCodeError::MissingField(field_name(26)))?;
+
+        let merchant_category_code = require(52)?;
+        if merchant_category_code.len() != 4 || !is_digits(merchant_category_code) {
+            return Err(invalid(52, "expected four digits"));
+        }
+        let currency = require(53)?;
+        if currency != "986" {
+            return Err(invalid(53, "expected 986 (BRL)"));
+        }
+        let amount = get(54).map(parse_amount).transpose()?;
+        let country_code = require(58)?;
+        if country_code.len() != 2 || !country_code.bytes().all(|b| b.is_ascii_uppercase()) {
+            return Err(invalid(58, "expected a two-letter country code"));
+        }
+        let merchant_name = require(59)?;
+        if merchant_name.trim().is_empty() {
+            return Err(invalid(59, "must not be empty"));
+        }
+        let merchant_city = require(60)?;
+        if merchant_city.trim().is_empty() {
+            return Err(invalid(60, "must not be empty"));
+        }
+
+        let txid = match get(62) {
+            Some(additional_data) => parse_txid(additional_data)?,
+            None => None,
+        };
+
+        Ok(Self {
+            point_of_initiation,
+            merchant_account,
+            merchant_category_code: merchant_category_code.to_owned(),
+            currency: currency.to_owned(),
+            amount,
+            country_code: country_code.to_owned(),
+            merchant_name: merchant_name.to_owned(),
+            merchant_city: merchant_city.to_owned(),
+            postal_code: get(61).map(str::to_owned),
+            txid,
+            crc: actual,
+        })
+    }
+
+    /// Whether the payer's bank must fetch the charge from `location`.
+    #[must_use]
+    pub const fn is_dynamic(&self) -> bool {
+        self.merchant_account.location.is_some()
+    }
+}
+
+impl FromStr for BrCode {
+    type Err = BrCodeError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::parse(input)
+    }
+}
+
+/// Builder for static Pix payloads, for counters and invoices where no COB
+/// exists. The result can be parsed back with [`BrCode::parse`].
+#[derive(Debug, Clone)]
+pub struct StaticBrCode {
+    key: String,
+    merchant_name: String,
+    merchant_city: String,
+    amount: Option<Brl>,
+    txid: Option<String>,
+    description: Option<String>,
+}
+
+impl StaticBrCode {
+    #[must_use]
+    pub fn new(
+        key: impl Into<String>,
+        merchant_name: impl Into<String>,
+        merchant_city: impl Into<String>,
+    ) -> Self {
+        Self {
+            key: key.into(),
+            merchant_name: merchant_name.into(),
+            merchant_city: merchant_city.into(),
+            amount: None,
+            txid: None,
+            description: None,
+        }
+    }
+
+    /// Fixes the amount; without it the payer chooses how much to send.
+    #[must_use]
+    pub const fn amount(mut self, amount: Brl) -> Self {
+        self.amount = Some(amount);
+        self
+    }
+
+    /// Up to 25 alphanumeric characters. Defaults to `***`.
+    #[must_use]
+    pub fn txid(mut self, txid: impl Into<String>) -> Self {
+        self.txid = Some(txid.into());
+        self
+    }
+
+    /// Printable ASCII; together with the key it must fit field `26` (99
+    /// characters).
+    #[must_use]
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Encodes the payload, rejecting values the BCB manual does not allow.
+    pub fn build(&self) -> Result<String, BrCodeError> {
+        let key = PixKey::parse(&self.key)
+            .map_err(|_| invalid(26, "Pix key must be a CPF, CNPJ, email, phone or EVP key"))?;
+        let description = self.description.as_deref().filter(|d| !d.is_empty());
+        if description.is_some_and(|d| !d.bytes().all(|b| b.is_ascii() && !b.is_ascii_control())) {
+            return Err(invalid(
+                26,
+                "description must contain only printable ASCII characters",
+            ));
+        }
+        // Each subfield adds a two-digit tag and a two-digit length.
+        let account_len = 4
+            + PIX_GUI.len()
+            + 4
+            + key.as_str().len()
+            + description.map_or(0, |description| 4 + description.len());
+        if account_len > 99 {
+            return Err(invalid(
+                26,
+                "Pix key and description together exceed 99 characters",
+            ));
+        }
+        check_ascii_text(
+            59,
+            &self.merchant_name,
+            25,
+            "must have at most 25 characters",
+        )?;
+        check_ascii_text(
+            60,
+            &self.merchant_city,
+            15,
+            "must have at most 15 characters",
+        )?;
+
+        let mut account = String::new();
+        push_field(&mut account, 0, PIX_GUI);
+        push_field(&mut account, 1, key.as_str());
+        if let Some(description) = description {
+            push_field(&mut account, 2, description);
+        }
+
+        let amount = match self.amount {
+            Some(amount) if amount.is_negative() || amount.is_zero() => {
+                return Err(invalid(54, "must be positive"));
+            }
+            Some(amount) => Some(amount.to_string()),
+            None => None,
+        };
+        if amount.as_ref().is_some_and(|amount| amount.len() > 13) {
+            return Err(invalid(54, "must have at most 13 characters"));
+        }
+
+        let txid = self.txid.as_deref().unwrap_or("***");
+        if txid != "***"
+            && (txid.is_empty()
+                || txid.len() > 25
+                || !txid.bytes().all(|b| b.is_ascii_alphanumeric()))
+        {
+            return Err(invalid(
+                62,
+                "txid must have 1 to 25 alphanumeric characters",
+            ));
+        }
+        let mut additional_data = String::new();
+        push_field(&mut additional_data, 5, txid);
+
+        let mut payload = String::new();
+        push_field(&mut payload, 0, "01");
+        push_field(&mut payload, 1, "11");
+        push_field(&mut payload, 26, &account);
+        push_field(&mut payload, 52, "0000");
+        push_field(&mut payload, 53, "986");
+        if let Some(amount) = &amount {
+            push_field(&mut payload, 54, amount);
+        }
+        push_field(&mut payload, 58, "BR");
+        push_field(&mut payload, 59, &self.merchant_name);
+        push_field(&mut payload, 60, &self.merchant_city);
+        push_field(&mut payload, 62, &additional_data);
+        payload.push_str(CRC_TAG);
+        let crc = crc16(payload.as_bytes());
+        payload.push_str(&format!("{crc:04X}"));
+
+        Ok(payload)
+    }
+}
+
+fn check_ascii_text(
+    tag: u8,
+    value: &str,
+    max_len: usize,
+    too_long: &'static str,
+) -> Result<(), BrCodeError> {
+    if value.trim().is_empty() {
+        return Err(invalid(tag, "must not be empty"));
+    }
+    if !value.bytes().all(|b| b.is_ascii() && !b.is_ascii_control()) {
+        return Err(invalid(tag, "must contain only printable ASCII characters"));
+    }
+    if value.len() > max_len {
+        return Err(invalid(tag, too_long));
+    }
+    Ok(())
+}
+
+/// Appends a TLV field. Callers keep `value` within 99 characters.
+fn push_field(out: &mut String, tag: u8, value: &str) {
+    out.push_str(&format!("{tag:02}{:02}{value}", value.chars().count()));
+}
+
+/// CRC16-CCITT (polynomial `0x1021`, initial value `0xFFFF`), as required by
+/// the BCB manual for field `63`.
+pub(crate) fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0xFFFF_u16, |crc, &byte| {
+        (0..8).fold(crc ^ (u16::from(byte) << 8), |crc, _| {
+            if crc & 0x8000 == 0 {
+                crc << 1
+            } else {
+                (crc << 1) ^ 0x1021
+            }
+        })
+    })
+}
+
+/// Splits `input` into `(offset, tag, value)` triples. Lengths count
+/// characters, so values with accented letters are read whole.
+fn read_fields(input: &str) -> Result<Vec<(usize, u8, &str)>, BrCodeError> {
+    let mut fields: Vec<(usize, u8, &str)> = Vec::new();
+    let mut offset = 0;
+
+    while offset < input.len() {
+        let malformed = |reason| BrCodeError::Malformed { offset, reason };
+        let header = input
+            .get(offset..offset + 4)
+            .filter(|header| is_digits(header))
+            .ok_or_else(|| malformed("expected a two-digit tag and length"))?;
+        let tag: u8 = header[..2].parse().expect("checked digits");
+        let len: usize = header[2..].parse().expect("checked digits");
+
+        let start = offset + 4;
+        let end = input[start..]
+            .char_indices()
+            .map(|(index, _)| start + index)
+            .chain(std::iter::once(input.len()))
+            .nth(len)
+            .ok_or_else(|| malformed("value is shorter than its length"))?;
+
+        if fields.iter().any(|&(_, existing, _)| existing == tag) {
+            return Err(malformed("duplicate tag"));
+        }
+        if fields.last().is_some_and(|&(_, last, _)| last == 63) {
+            return Err(malformed("CRC must be the last field"));
+        }
+        fields.push((offset, tag, &input[start..end]));
+        offset = end;
+    }
+
+    Ok(fields)
+}
+
+fn parse_merchant_account(template: &str) -> Result<Option<PixMerchantAccount>, BrCodeError> {
+    let invalid_template = || invalid(26, "malformed merchant account template");
+    let fields = read_fields(template).map_err(|_| invalid_template())?;
+    let get = |tag: u8| {
+        fields
+            .iter()
+            .find(|&&(_, field, _)| field == tag)
+            .map(|&(_, _, value)| value.to_owned())
+    };
+
+    let Some(gui) = get(0) else {
+        return Err(invalid_template());
+    };
+    if !gui.eq_ignore_ascii_case(PIX_GUI) {
+        return Ok(None);
+    }
+
+    let account = PixMerchantAccount {
+        gui,
+        key: get(1),
+        description: get(2),
+        location: get(25),
+    };
+    if account.key.is_none() && account.location.is_none() {
+        return Err(invalid(26, "expected a Pix key or a location"));
+    }
+    Ok(Some(account))
+}
+
+fn parse_amount(value: &str) -> Result<Brl, BrCodeError> {
+    let amount = value
+        .parse::<Brl>()
+        .map_err(|_| invalid(54, "expected a decimal amount with up to two places"))?;
+    if amount.is_negative() || value.starts_with('+') {
+        return Err(invalid(54, "must be positive"));
+    }
+    Ok(amount)
+}
+
+fn parse_txid(additional_data: &str) -> Result<Option<String>, BrCodeError> {
+    let fields =
+        read_fields(additional_data).map_err(|_| invalid(62, "malformed additional data"))?;
+    match fields.iter().find(|&&(_, tag, _)| tag == 5) {
+        None | Some((_, _, "***")) => Ok(None),
+        Some(&(_, _, txid)) if txid.bytes().all(|b| b.is_ascii_alphanumeric()) => {
+            Ok(Some(txid.to_owned()))
+        }
+        Some(_) => Err(invalid(62, "txid must be alphanumeric")),
+    }
+}
+
+fn is_digits(value: &str) -> bool {
+    !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit())
+}
+
+const fn invalid(tag: u8, reason: &'static str) -> BrCodeError {
+    BrCodeError::InvalidField {
+        field: field_name(tag),
+        reason,
+    }
+}
+
+const fn field_name(tag: u8) -> &'static str {
+    match tag {
+        0 => "payload format indicator (00)",
+        1 => "point of initiation method (01)",
+        26 => "merchant account information (26)",
+        52 => "merchant category code (52)",
+        53 => "transaction currency (53)",
+        54 => "transaction amount (54)",
+        58 => "country code (58)",
+        59 => "merchant name (59)",
+        60 => "merchant city (60)",
+        62 => "additional data (62)",
+        63 => "CRC (63)",
+        _ => "unknown field",
+    }
+}
+
+impl Display for BrCodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Malformed { offset, reason } => {
+                write!(f, "malformed BR Code at offset {offset}: {reason}")
+            }
+            Self::MissingField(field) => write!(f, "BR Code is missing {field}"),
+            Self::InvalidField { field, reason } => {
+                write!(f, "invalid BR Code {field}: {reason}")
+            }
+            Self::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "BR Code CRC mismatch: expected {expected:04X}, found {actual:04X}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BrCodeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{BrCode, BrCodeError, PointOfInitiation, StaticBrCode, crc16};
+    use crate::money::Brl;
+
+    /// Static example from the BCB "Manual de Padrões para Iniciação do Pix".
+    const MANUAL_SAMPLE: &str = "00020126580014br.gov.bcb.pix0136123e4567-e12b-12d1-a456-426655440000\
+        5204000053039865802BR5913Fulano de Tal6008BRASILIA62070503***63041D3D";
+
+    #[test]
+    fn crc16_matches_the_ccitt_false_check_value() {
+        assert_eq!(crc16(b"123456789"), 0x29B1);
+    }
+
+    #[test]
+    fn parses_the_manual_sample() {
+        let code = BrCode::parse(MANUAL_SAMPLE).unwrap();
+        assert_eq!(code.crc, 0x1D3D);
+        assert_eq!(code.point_of_initiation, None);
+        assert_eq!(code.merchant_account.gui, "br.gov.bcb.pix");
+        assert_eq!(
+            code.merchant_account.key.as_deref(),
+            Some("123e4567-e12b-12d1-a456-426655440000")
+        );
+        assert_eq!(code.merchant_category_code, "0000");
+        assert_eq!(code.currency, "986");
+        assert_eq!(code.amount, None);
+        assert_eq!(code.country_code, "BR");
+        assert_eq!(code.merchant_name, "Fulano de Tal");
+        assert_eq!(code.merchant_city, "BRASILIA");
+        assert_eq!(code.txid, None);
+        assert!(!code.is_dynamic());
+    }
+
+    #[test]
+    fn rejects_a_wrong_crc() {
+        let tampered = MANUAL_SAMPLE.replace("63041D3D", "63041D3E");
+        assert_eq!(
+            BrCode::parse(&tampered),
+            Err(BrCodeError::ChecksumMismatch {
+                expected: 0x1D3D,
+                actual: 0x1D3E,
+            })
+        );
+
+        let renamed = MANUAL_SAMPLE.replace("Fulano de Tal", "Fulano de Tai");
+        assert!(matches!(
+            BrCode::parse(&renamed),
+            Err(BrCodeError::ChecksumMismatch { actual: 0x1D3D, .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_broken_structure() {
+        assert!(matches!(
+            BrCode::parse("000201"),
+            Err(BrCodeError::MissingField(_))
+        ));
+        assert!(matches!(
+            BrCode::parse(&MANUAL_SAMPLE[..MANUAL_SAMPLE.len() - 2]),
+            Err(BrCodeError::Malformed { .. })
+        ));
+    }
+
+    #[test]
+    fn builds_the_manual_sample() {
+        let payload = StaticBrCode::new(
+            "123e4567-e12b-12d1-a456-426655440000",
+            "Fulano de Tal",
+            "BRASILIA",
+        )
+        .build()
+        .unwrap();
+        // The builder also sends the point of initiation (`010211`); without
+        // it, everything up to the CRC value matches the manual.
+        let code = BrCode::parse(&payload).unwrap();
+        assert_eq!(code.point_of_initiation, Some(PointOfInitiation::Static));
+        let without_poi = payload.replacen("010211", "", 1);
+        assert_eq!(
+            without_poi[..without_poi.len() - 4],
+            MANUAL_SAMPLE[..MANUAL_SAMPLE.len() - 4]
+        );
+    }
+
+    #[test]
+    fn round_trips_amount_and_txid() {
+        let payload = StaticBrCode::new("fulano@example.com", "Fulano de Tal", "SAO PAULO")
+            .amount(Brl::from_cents(1050))
+            .txid("PEDIDO42")
+            .description("Pedido 42")
+            .build()
+            .unwrap();
+        let code = BrCode::parse(&payload).unwrap();
+        assert_eq!(code.amount, Some(Brl::from_cents(1050)));
+        assert_eq!(code.txid.as_deref(), Some("PEDIDO42"));
+        assert_eq!(
+            code.merchant_account.description.as_deref(),
+            Some("Pedido 42")
+        );
+        assert_eq!(format!("{:04X}", code.crc), payload[payload.len() - 4..]);
+    }
+
+    #[test]
+    fn builder_normalizes_the_key() {
+        let payload = StaticBrCode::new(" +55 (11) 98765-4321 ", "Fulano de Tal", "SAO PAULO")
+            .build()
+            .unwrap();
+        let code = BrCode::parse(&payload).unwrap();
+        assert_eq!(code.merchant_account.key.as_deref(), Some("+5511987654321"));
+    }
+
+    #[test]
+    fn builder_rejects_invalid_fields() {
+        let builder = StaticBrCode::new("fulano@example.com", "Fulano de Tal", "SAO PAULO");
+        assert!(builder.clone().amount(Brl::ZERO).build().is_err());
+        assert!(builder.clone().txid("has space").build().is_err());
+        assert!(builder.clone().description("Pedido nº 42").build().is_err());
+        assert!(builder.clone().description("x".repeat(56)).build().is_err());
+        assert!(builder.clone().description("x".repeat(55)).build().is_ok());
+        for key in ["", "fulano", "529.982.247-52", "11987654321"] {
+            let builder = StaticBrCode::new(key, "Fulano de Tal", "SAO PAULO");
+            assert!(builder.build().is_err(), "{key:?}");
+        }
+        assert!(
+            StaticBrCode::new(
+                "fulano@example.com",
+                "Fulano de Tal",
+                "A CITY NAME TOO LONG"
+            )
+            .build()
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,969 @@
+use crate::datetime::Date;
+use crate::error::Error;
+use crate::money::Brl;
+use crate::pix_key::PixKey;
+use crate::types::{
+    BillingAddress, BillingBankingBillet, BillingBilletConfigurations, BillingChargeCreateRequest,
+    BillingChargeMetadata, BillingChargeOneStepRequest, BillingCreditCard, BillingCustomer,
+    BillingItem, BillingJuridicalPerson, BillingPayment, BillingShipping, CarnetCreateRequest,
+    CarnetCustomer, CarnetMetadataRequest, CobCalendario, CobInfoAdicional, CobPayload, CobValor,
+    CobvCalendario, CobvDevedor, CobvPayload, Devedor,
+};
+
+const fn invalid(field: &'static str, reason: &'static str) -> Error {
+    Error::BuilderInvalidField { field, reason }
+}
+
+fn check_len(field: &'static str, value: &str, max: usize) -> Result<(), Error> {
+    if value.trim().is_empty() {
+        return Err(invalid(field, "must not be empty"));
+    }
+    if value.chars().count() > max {
+        return Err(invalid(field, "is too long"));
+    }
+    Ok(())
+}
+
+fn check_optional_len(field: &'static str, value: Option<&str>, max: usize) -> Result<(), Error> {
+    value.map_or(Ok(()), |value| check_len(field, value, max))
+}
+
+fn check_positive(field: &'static str, value: Brl) -> Result<(), Error> {
+    if value.is_negative() || value.is_zero() {
+        return Err(invalid(field, "must be greater than zero"));
+    }
+    Ok(())
+}
+
+/// Checks what the Pix API enforces for both COB and COBV.
+fn check_pix_common(
+    original: Brl,
+    solicitacao_pagador: Option<&str>,
+    info_adicionais: &[CobInfoAdicional],
+    devedor: Option<&Devedor>,
+) -> Result<(), Error> {
+    check_positive("valor.original", original)?;
+    check_optional_len("solicitacao_pagador", solicitacao_pagador, 140)?;
+    if info_adicionais.len() > 50 {
+        return Err(invalid("info_adicionais", "at most 50 entries are allowed"));
+    }
+    for info in info_adicionais {
+        check_len("info_adicionais.nome", &info.nome, 50)?;
+        check_len("info_adicionais.valor", &info.valor, 200)?;
+    }
+    if let Some(devedor) = devedor {
+        check_len("devedor.nome", devedor.nome(), 200)?;
+    }
+    Ok(())
+}
+
+fn check_items(items: &[BillingItem]) -> Result<(), Error> {
+    if items.is_empty() {
+        return Err(Error::BuilderMissingField("items"));
+    }
+    for item in items {
+        check_len("items.name", &item.name, 255)?;
+        check_positive("items.value", item.value)?;
+        if item.amount < 1 {
+            return Err(invalid("items.amount", "must be at least 1"));
+        }
+    }
+    Ok(())
+}
+
+fn check_metadata(notification_url: Option<&str>, custom_id: Option<&str>) -> Result<(), Error> {
+    check_optional_len("metadata.notification_url", notification_url, 255)?;
+    check_optional_len("metadata.custom_id", custom_id, 255)
+}
+
+fn metadata(
+    notification_url: Option<String>,
+    custom_id: Option<String>,
+) -> Option<BillingChargeMetadata> {
+    (notification_url.is_some() || custom_id.is_some()).then_some(BillingChargeMetadata {
+        notification_url,
+        custom_id,
+    })
+}
+
+impl BillingItem {
+    #[must_use]
+    pub fn new(name: impl Into<String>, value: Brl, amount: i32) -> Self {
+        Self {
+            name: name.into(),
+            value,
+            amount,
+            marketplace: None,
+        }
+    }
+}
+
+impl CobInfoAdicional {
+    #[must_use]
+    pub fn new(nome: impl Into<String>, valor: impl Into<String>) -> Self {
+        Self {
+            nome: nome.into(),
+            valor: valor.into(),
+        }
+    }
+}
+
+// ========== Pix ==========
+
+pub struct CobPayloadBuilder {
+    chave: PixKey,
+    valor: Brl,
+    expiracao: Option<u32>,
+    devedor: Option<Devedor>,
+    solicitacao_pagador: Option<String>,
+    info_adicionais: Vec<CobInfoAdicional>,
+}
+
+impl CobPayload {
+    #[must_use]
+    pub const fn builder(chave: PixKey, valor: Brl) -> CobPayloadBuilder {
+        CobPayloadBuilder {
+            chave,
+            valor,
+            expiracao: None,
+            devedor: None,
+            solicitacao_pagador: None,
+            info_adicionais: Vec::new(),
+        }
+    }
+}
+
+impl CobPayloadBuilder {
+    /// Seconds until the charge expires, counted from its creation.
+    #[must_use]
+    pub const fn expiracao(mut self, seconds: u32) -> Self {
+        self.expiracao = Some(seconds);
+        self
+    }
+
+    #[must_use]
+    pub fn devedor(mut self, devedor: Devedor) -> Self {
+        self.devedor = Some(devedor);
+        self
+    }
+
+    /// Message shown to the payer, up to 140 characters.
+    #[must_use]
+    pub fn solicitacao_pagador(mut self, value: impl Into<String>) -> Self {
+        self.solicitacao_pagador = Some(value.into());
+        self
+    }
+
+    /// Adds an `infoAdicionais` entry; up to 50 are allowed.
+    #[must_use]
+    pub fn info(mut self, nome: impl Into<String>, valor: impl Into<String>) -> Self {
+        self.info_adicionais
+            .push(CobInfoAdicional::new(nome, valor));
+        self
+    }
+
+    pub fn build(self) -> Result<CobPayload, Error> {
+        if self.expiracao == Some(0) {
+            return Err(invalid("calendario.expiracao", "must be greater than zero"));
+        }
+        check_pix_common(
+            self.valor,
+            self.solicitacao_pagador.as_deref(),
+            &self.info_adicionais,
+            self.devedor.as_ref(),
+        )?;
+
+        Ok(CobPayload {
+            calendario: CobCalendario {
+                expiracao: self.expiracao,
+            },
+            devedor: self.devedor,
+            valor: CobValor {
+                original: self.valor,
+                multa: None,
+                juros: None,
+                desconto: None,
+                abatimento: None,
+            },
+            chave: self.chave,
+            solicitacao_pagador: self.solicitacao_pagador,
+            info_adicionais: (!self.info_adicionais.is_empty()).then_some(self.info_adicionais),
+        })
+    }
+}
+
+pub struct CobvPayloadBuilder {
+    chave: PixKey,
+    valor: CobValor,
+    data_de_vencimento: Date,
+    validade_apos_vencimento: i32,
+    devedor: Option<CobvDevedor>,
+    solicitacao_pagador: Option<String>,
+    info_adicionais: Vec<CobInfoAdicional>,
+}
+
+impl CobvPayload {
+    /// `validade_apos_vencimento` defaults to 30 days, as in the API.
+    #[must_use]
+    pub const fn builder(
+        chave: PixKey,
+        valor: Brl,
+        data_de_vencimento: Date,
+    ) -> CobvPayloadBuilder {
+        CobvPayloadBuilder {
+            chave,
+            valor: CobValor {
+                original: valor,
+                multa: None,
+                juros: None,
+                desconto: None,
+                abatimento: None,
+            },
+            data_de_vencimento,
+            validade_apos_vencimento: 30,
+            devedor: None,
+            solicitacao_pagador: None,
+            info_adicionais: Vec::new(),
+        }
+    }
+}
+
+impl CobvPayloadBuilder {
+    /// Required: the API rejects COBVs without a debtor.
+    #[must_use]
+    pub fn devedor(mut self, devedor: impl Into<CobvDevedor>) -> Self {
+        self.devedor = Some(devedor.into());
+        self
+    }
+
+    /// Days the charge can still be paid after its due date.
+    #[must_use]
+    pub const fn validade_apos_vencimento(mut self, days: i32) -> Self {
+        self.validade_apos_vencimento = days;
+        self
+    }
+
+    #[must_use]
+    pub const fn multa(mut self, value: Brl) -> Self {
+        self.valor.multa = Some(value);
+        self
+    }
+
+    #[must_use]
+    pub const fn juros(mut self, value: Brl) -> Self {
+        self.valor.juros = Some(value);
+        self
+    }
+
+    #[must_use]
+    pub const fn desconto(mut self, value: Brl) -> Self {
+        self.valor.desconto = Some(value);
+        self
+    }
+
+    #[must_use]
+    pub const fn abatimento(mut self, value: Brl) -> Self {
+        self.valor.abatimento = Some(value);
+        self
+    }
+
+    #[must_use]
+    pub fn solicitacao_pagador(mut self, value: impl Into<String>) -> Self {
+        self.solicitacao_pagador = Some(value.into());
+        self
+    }
+
+    #[must_use]
+    pub fn info(mut self, nome: impl Into<String>, valor: impl Into<String>) -> Self {
+        self.info_adicionais
+            .push(CobInfoAdicional::new(nome, valor));
+        self
+    }
+
+    pub fn build(self) -> Result<CobvPayload, Error> {
+        let devedor = self.devedor.ok_or(Error::BuilderMissingField("devedor"))?;
+        check_pix_common(
+            self.valor.original,
+            self.solicitacao_pagador.as_deref(),
+            &self.info_adicionais,
+            Some(&devedor.pessoa),
+        )?;
+        if self.validade_apos_vencimento < 0 {
+            return Err(invalid("validade_apos_vencimento", "must not be negative"));
+        }
+        for (field, value) in [
+            ("valor.multa", self.valor.multa),
+            ("valor.juros", self.valor.juros),
+            ("valor.desconto", self.valor.desconto),
+            ("valor.abatimento", self.valor.abatimento),
+        ] {
+            if value.is_some_and(Brl::is_negative) {
+                return Err(invalid(field, "must not be negative"));
+            }
+        }
+        check_optional_len("devedor.logradouro", devedor.logradouro.as_deref(), 200)?;
+        check_optional_len("devedor.cidade", devedor.cidade.as_deref(), 200)?;
+        check_optional_len("devedor.email", devedor.email.as_deref(), 77)?;
+        if let Some(uf) = &devedor.uf
+            && (uf.len() != 2 || !uf.bytes().all(|b| b.is_ascii_alphabetic()))
+        {
+            return Err(invalid("devedor.uf", "expected a two-letter state code"));
+        }
+        if let Some(cep) = &devedor.cep
+            && (cep.len() != 8 || !cep.bytes().all(|b| b.is_ascii_digit()))
+        {
+            return Err(invalid("devedor.cep", "expected 8 digits"));
+        }
+
+        Ok(CobvPayload {
+            calendario: CobvCalendario {
+                data_de_vencimento: self.data_de_vencimento,
+                validade_apos_vencimento: self.validade_apos_vencimento,
+            },
+            devedor: Some(devedor),
+            valor: self.valor,
+            chave: self.chave,
+            solicitacao_pagador: self.solicitacao_pagador,
+            info_adicionais: (!self.info_adicionais.is_empty()).then_some(self.info_adicionais),
+        })
+    }
+}
+
+// ========== Billing ==========
+
+/// Builds a charge (`POST /v1/charge`) to be paid later with
+/// `billing_charge_pay`.
+#[derive(Default)]
+pub struct BillingChargeCreateRequestBuilder {
+    items: Vec<BillingItem>,
+    notification_url: Option<String>,
+    custom_id: Option<String>,
+}
+
+impl BillingChargeCreateRequest {
+    #[must_use]
+    pub fn builder() -> BillingChargeCreateRequestBuilder {
+        BillingChargeCreateRequestBuilder::default()
+    }
+}
+
+impl BillingChargeCreateRequestBuilder {
+    #[must_use]
+    pub fn item(mut self, item: BillingItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    #[must_use]
+    pub fn notification_url(mut self, value: impl Into<String>) -> Self {
+        self.notification_url = Some(value.into());
+        self
+    }
+
+    #[must_use]
+    pub fn custom_id(mut self, value: impl Into<String>) -> Self {
+        self.custom_id = Some(value.into());
+        self
+    }
+
+    pub fn build(self) -> Result<BillingChargeCreateRequest, Error> {
+        check_items(&self.items)?;
+        check_metadata(self.notification_url.as_deref(), self.custom_id.as_deref())?;
+        Ok(BillingChargeCreateRequest {
+            items: self.items,
+            metadata: metadata(self.notification_url, self.custom_id),
+        })
+    }
+}
+
+enum PaymentMethod {
+    BankingBillet(BillingBankingBillet),
+    CreditCard(BillingCreditCard),
+}
+
+/// Builds a one-step charge (`POST /v1/charge/one-step`) paid by banking
+/// billet or credit card.
+#[derive(Default)]
+pub struct BillingChargeOneStepRequestBuilder {
+    items: Vec<BillingItem>,
+    shippings: Vec<BillingShipping>,
+    payment: Option<PaymentMethod>,
+    conflicting_payment: bool,
+    billet_message: Option<String>,
+    billet_configurations: Option<BillingBilletConfigurations>,
+    notification_url: Option<String>,
+    custom_id: Option<String>,
+}
+
+impl BillingChargeOneStepRequest {
+    #[must_use]
+    pub fn builder() -> BillingChargeOneStepRequestBuilder {
+        BillingChargeOneStepRequestBuilder::default()
+    }
+}
+
+impl BillingChargeOneStepRequestBuilder {
+    #[must_use]
+    pub fn item(mut self, item: BillingItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    #[must_use]
+    pub fn shipping(mut self, name: impl Into<String>, value: Brl) -> Self {
+        self.shippings.push(BillingShipping {
+            name: name.into(),
+            value,
+        });
+        self
+    }
+
+    /// Pays by banking billet. `customer` needs a name and a CPF or juridical
+    /// person.
+    #[must_use]
+    pub fn banking_billet(self, customer: BillingCustomer, expire_at: Date) -> Self {
+        self.set_payment(PaymentMethod::BankingBillet(BillingBankingBillet {
+            expire_at: Some(expire_at),
+            customer,
+            configurations: None,
+            message: None,
+        }))
+    }
+
+    /// Fine (`1000` is 10%) and daily interest (`33` is 0.033%) for a billet.
+    #[must_use]
+    pub const fn billet_configurations(mut self, fine: Option<i64>, interest: Option<i64>) -> Self {
+        self.billet_configurations = Some(BillingBilletConfigurations { fine, interest });
+        self
+    }
+
+    /// Message printed on the billet, up to 80 characters.
+    #[must_use]
+    pub fn billet_message(mut self, message: impl Into<String>) -> Self {
+        self.billet_message = Some(message.into());
+        self
+    }
+
+    /// Pays by credit card with a token from Efí's JavaScript library.
+    #[must_use]
+    pub fn credit_card(
+        self,
+        customer: BillingCustomer,
+        payment_token: impl Into<String>,
+        installments: i32,
+        billing_address: BillingAddress,
+    ) -> Self {
+        self.set_payment(PaymentMethod::CreditCard(BillingCreditCard {
+            customer,
+            installments,
+            payment_token: payment_token.into(),
+            billing_address: Some(billing_address),
+        }))
+    }
+
+    #[must_use]
+    pub fn notification_url(mut self, value: impl Into<String>) -> Self {
+        self.notification_url = Some(value.into());
+        self
+    }
+
+    #[must_use]
+    pub fn custom_id(mut self, value: impl Into<String>) -> Self {
+        self.custom_id = Some(value.into());
+        self
+    }
+
+    fn set_payment(mut self, payment: PaymentMethod) -> Self {
+        self.conflicting_payment |= self.payment.is_some();
+        self.payment = Some(payment);
+        self
+    }
+
+    pub fn build(self) -> Result<BillingChargeOneStepRequest, Error> {
+        if self.conflicting_payment {
+            return Err(Error::BuilderConflict(
+                "choose either banking_billet or credit_card, not both",
+            ));
+        }
+        check_items(&self.items)?;
+        for shipping in &self.shippings {
+            check_len("shippings.name", &shipping.name, 255)?;
+            check_positive("shippings.value", shipping.value)?;
+        }
+        check_metadata(self.notification_url.as_deref(), self.custom_id.as_deref())?;
+
+        let payment = match self.payment.ok_or(Error::BuilderMissingField("payment"))? {
+            PaymentMethod::BankingBillet(mut billet) => {
+                check_billing_customer(
+                    billet.customer.name.as_deref(),
+                    billet.customer.cpf.is_some(),
+                    billet.customer.juridical_person.as_ref(),
+                )?;
+                check_optional_len("billet_message", self.billet_message.as_deref(), 80)?;
+                if let Some(configurations) = &self.billet_configurations {
+                    check_billet_configurations(configurations)?;
+                }
+                billet.message = self.billet_message;
+                billet.configurations = self.billet_configurations;
+                BillingPayment {
+                    banking_billet: Some(billet),
+                    credit_card: None,
+                }
+            }
+            PaymentMethod::CreditCard(card) => {
+                if self.billet_message.is_some() || self.billet_configurations.is_some() {
+                    return Err(Error::BuilderConflict(
+                        "billet_message and billet_configurations only apply to banking billets",
+                    ));
+                }
+                check_billing_customer(
+                    card.customer.name.as_deref(),
+                    card.customer.cpf.is_some(),
+                    card.customer.juridical_person.as_ref(),
+                )?;
+                for (field, value) in [
+                    ("customer.email", &card.customer.email),
+                    ("customer.phone_number", &card.customer.phone_number),
+                    ("customer.birth", &card.customer.birth),
+                ] {
+                    if value.is_none() {
+                        return Err(Error::BuilderMissingField(field));
+                    }
+                }
+                check_len("credit_card.payment_token", &card.payment_token, 255)?;
+                if !(1..=12).contains(&card.installments) {
+                    return Err(invalid(
+                        "credit_card.installments",
+                        "must be between 1 and 12",
+                    ));
+                }
+                BillingPayment {
+                    banking_billet: None,
+                    credit_card: Some(card),
+                }
+            }
+        };
+
+        Ok(BillingChargeOneStepRequest {
+            items: self.items,
+            shippings: (!self.shippings.is_empty()).then_some(self.shippings),
+            payment,
+            metadata: metadata(self.notification_url, self.custom_id),
+        })
+    }
+}
+
+/// A company needs its corporate name; anyone else needs a name and a
+/// document (a CPF, or on carnets also a CNPJ).
+fn check_billing_customer(
+    name: Option<&str>,
+    has_document: bool,
+    juridical_person: Option<&BillingJuridicalPerson>,
+) -> Result<(), Error> {
+    match juridical_person {
+        Some(company) => check_len(
+            "customer.juridical_person.corporate_name",
+            &company.corporate_name,
+            255,
+        ),
+        None => {
+            check_len(
+                "customer.name",
+                name.ok_or(Error::BuilderMissingField("customer.name"))?,
+                255,
+            )?;
+            if !has_document {
+                return Err(Error::BuilderMissingField("customer.cpf"));
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Fine is capped at 10% (`1000`) and interest at 0.33% a day (`330`).
+fn check_billet_configurations(configurations: &BillingBilletConfigurations) -> Result<(), Error> {
+    if configurations
+        .fine
+        .is_some_and(|fine| !(0..=1000).contains(&fine))
+    {
+        return Err(invalid("configurations.fine", "must be between 0 and 1000"));
+    }
+    if configurations
+        .interest
+        .is_some_and(|interest| !(0..=330).contains(&interest))
+    {
+        return Err(invalid(
+            "configurations.interest",
+            "must be between 0 and 330",
+        ));
+    }
+    Ok(())
+}
+
+pub struct CarnetCreateRequestBuilder {
+    customer: CarnetCustomer,
+    expire_at: Date,
+    repeats: i32,
+    items: Vec<BillingItem>,
+    message: Option<String>,
+    split_items: Option<bool>,
+    notification_url: Option<String>,
+    custom_id: Option<String>,
+    configurations: Option<BillingBilletConfigurations>,
+}
+
+impl CarnetCreateRequest {
+    /// `expire_at` is the due date of the first parcel; `repeats` is the
+    /// number of parcels, from 2 to 12.
+    #[must_use]
+    pub const fn builder(
+        customer: CarnetCustomer,
+        expire_at: Date,
+        repeats: i32,
+    ) -> CarnetCreateRequestBuilder {
+        CarnetCreateRequestBuilder {
+            customer,
+            expire_at,
+            repeats,
+            items: Vec::new(),
+            message: None,
+            split_items: None,
+            notification_url: None,
+            custom_id: None,
+            configurations: None,
+        }
+    }
+}
+
+impl CarnetCreateRequestBuilder {
+    #[must_use]
+    pub fn item(mut self, item: BillingItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    #[must_use]
+    pub fn message(mut self, value: impl Into<String>) -> Self {
+        self.message = Some(value.into());
+        self
+    }
+
+    /// Splits the item total across parcels instead of repeating it on each.
+    #[must_use]
+    pub const fn split_items(mut self, value: bool) -> Self {
+        self.split_items = Some(value);
+        self
+    }
+
+    #[must_use]
+    pub const fn configurations(mut self, fine: Option<i64>, interest: Option<i64>) -> Self {
+        self.configurations = Some(BillingBilletConfigurations { fine, interest });
+        self
+    }
+
+    #[must_use]
+    pub fn notification_url(mut self, value: impl Into<String>) -> Self {
+        self.notification_url = Some(value.into());
+        self
+    }
+
+    #[must_use]
+    pub fn custom_id(mut self, value: impl Into<String>) -> Self {
+        self.custom_id = Some(value.into());
+        self
+    }
+
+    pub fn build(self) -> Result<CarnetCreateRequest, Error> {
+        check_items(&self.items)?;
+        if !(2..=12).contains(&self.repeats) {
+            return Err(invalid("repeats", "must be between 2 and 12"));
+        }
+        check_optional_len("message", self.message.as_deref(), 80)?;
+        check_metadata(self.notification_url.as_deref(), self.custom_id.as_deref())?;
+        if let Some(configurations) = &self.configurations {
+            check_billet_configurations(configurations)?;
+        }
+        check_billing_customer(
+            self.customer.name.as_deref(),
+            self.customer.cpf.is_some() || self.customer.cnpj.is_some(),
+            self.customer.juridical_person.as_ref(),
+        )?;
+
+        Ok(CarnetCreateRequest {
+            items: self.items,
+            customer: self.customer,
+            expire_at: self.expire_at,
+            message: self.message,
+            repeats: self.repeats,
+            split_items: self.split_items,
+            metadata: metadata(self.notification_url, self.custom_id).map(|metadata| {
+                CarnetMetadataRequest {
+                    notification_url: metadata.notification_url,
+                    custom_id: metadata.custom_id,
+                }
+            }),
+            configurations: self.configurations,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Debug;
+
+    use super::*;
+
+    /// The field a builder error points at.
+    fn field<T: Debug>(result: Result<T, Error>) -> &'static str {
+        match result {
+            Err(Error::BuilderMissingField(field) | Error::BuilderInvalidField { field, .. }) => {
+                field
+            }
+            other => panic!("expected a builder field error, got {other:?}"),
+        }
+    }
+
+    fn chave() -> PixKey {
+        PixKey::email("loja@example.com").unwrap()
+    }
+
+    fn devedor(nome: &str) -> Devedor {
+        Devedor::Fisica {
+            cpf: "52998224725".parse().unwrap(),
+            nome: nome.to_owned(),
+        }
+    }
+
+    fn date() -> Date {
+        "2030-01-31".parse().unwrap()
+    }
+
+    fn item() -> BillingItem {
+        BillingItem::new("Plano mensal", Brl::from_cents(4_990), 1)
+    }
+
+    fn customer() -> BillingCustomer {
+        BillingCustomer {
+            name: Some("Fulano de Tal".to_owned()),
+            cpf: Some("52998224725".parse().unwrap()),
+            email: Some("fulano@example.com".to_owned()),
+            birth: Some("1990-01-01".to_owned()),
+            phone_number: Some("11987654321".to_owned()),
+            address: None,
+            juridical_person: None,
+        }
+    }
+
+    fn carnet_customer() -> CarnetCustomer {
+        CarnetCustomer {
+            name: Some("Fulano de Tal".to_owned()),
+            cpf: Some("52998224725".parse().unwrap()),
+            cnpj: None,
+            email: None,
+            phone_number: None,
+            birth: None,
+            juridical_person: None,
+        }
+    }
+
+    fn address() -> BillingAddress {
+        BillingAddress {
+            street: Some("Rua A".to_owned()),
+            number: Some("1".to_owned()),
+            neighborhood: Some("Centro".to_owned()),
+            zipcode: Some("01001000".to_owned()),
+            city: Some("São Paulo".to_owned()),
+            complement: None,
+            state: Some("SP".to_owned()),
+        }
+    }
+
+    #[test]
+    fn cob_rejects_invalid_fields() {
+        let cob = || CobPayload::builder(chave(), Brl::from_cents(100));
+        assert!(cob().info("Pedido", "42").build().is_ok());
+
+        let zero = CobPayload::builder(chave(), Brl::from_cents(0));
+        assert_eq!(field(zero.build()), "valor.original");
+        assert_eq!(field(cob().expiracao(0).build()), "calendario.expiracao");
+        assert_eq!(
+            field(cob().solicitacao_pagador("x".repeat(141)).build()),
+            "solicitacao_pagador"
+        );
+        assert_eq!(field(cob().devedor(devedor(" ")).build()), "devedor.nome");
+        assert_eq!(field(cob().info("", "42").build()), "info_adicionais.nome");
+        let too_many = (0..51).fold(cob(), |cob, i| cob.info(format!("campo{i}"), "x"));
+        assert_eq!(field(too_many.build()), "info_adicionais");
+    }
+
+    #[test]
+    fn cobv_rejects_invalid_fields() {
+        let cobv = || CobvPayload::builder(chave(), Brl::from_cents(100), date());
+        let with_devedor = || cobv().devedor(devedor("Fulano de Tal"));
+        assert!(with_devedor().multa(Brl::from_cents(0)).build().is_ok());
+
+        assert_eq!(field(cobv().build()), "devedor");
+        assert_eq!(
+            field(with_devedor().validade_apos_vencimento(-1).build()),
+            "validade_apos_vencimento"
+        );
+        assert_eq!(
+            field(with_devedor().juros(Brl::from_cents(-1)).build()),
+            "valor.juros"
+        );
+
+        let mut address = CobvDevedor::from(devedor("Fulano de Tal"));
+        address.uf = Some("São Paulo".to_owned());
+        assert_eq!(field(cobv().devedor(address.clone()).build()), "devedor.uf");
+        address.uf = Some("SP".to_owned());
+        address.cep = Some("01001-000".to_owned());
+        assert_eq!(field(cobv().devedor(address).build()), "devedor.cep");
+    }
+
+    #[test]
+    fn charge_create_checks_items_and_metadata() {
+        assert!(
+            BillingChargeCreateRequest::builder()
+                .item(item())
+                .build()
+                .is_ok()
+        );
+        assert_eq!(
+            field(BillingChargeCreateRequest::builder().build()),
+            "items"
+        );
+
+        let builder = || BillingChargeCreateRequest::builder().item(item());
+        for (item, expected) in [
+            (BillingItem::new("", Brl::from_cents(100), 1), "items.name"),
+            (
+                BillingItem::new("Plano", Brl::from_cents(0), 1),
+                "items.value",
+            ),
+            (
+                BillingItem::new("Plano", Brl::from_cents(100), 0),
+                "items.amount",
+            ),
+        ] {
+            assert_eq!(field(builder().item(item).build()), expected);
+        }
+        assert_eq!(
+            field(builder().custom_id("x".repeat(256)).build()),
+            "metadata.custom_id"
+        );
+        assert_eq!(
+            field(builder().notification_url("").build()),
+            "metadata.notification_url"
+        );
+    }
+
+    #[test]
+    fn one_step_checks_payment_and_customer() {
+        let builder = || BillingChargeOneStepRequest::builder().item(item());
+        assert!(builder().banking_billet(customer(), date()).build().is_ok());
+
+        assert_eq!(field(builder().build()), "payment");
+        let both = builder().banking_billet(customer(), date()).credit_card(
+            customer(),
+            "token",
+            1,
+            address(),
+        );
+        assert!(matches!(both.build(), Err(Error::BuilderConflict(_))));
+        let billet_on_card = builder()
+            .credit_card(customer(), "token", 1, address())
+            .billet_message("Obrigado");
+        assert!(matches!(
+            billet_on_card.build(),
+            Err(Error::BuilderConflict(_))
+        ));
+
+        let mut nameless = customer();
+        nameless.name = None;
+        assert_eq!(
+            field(builder().banking_billet(nameless, date()).build()),
+            "customer.name"
+        );
+        let mut undocumented = customer();
+        undocumented.cpf = None;
+        assert_eq!(
+            field(builder().banking_billet(undocumented, date()).build()),
+            "customer.cpf"
+        );
+        let mut company = customer();
+        company.name = None;
+        company.cpf = None;
+        company.juridical_person = Some(BillingJuridicalPerson {
+            corporate_name: "Loja LTDA".to_owned(),
+            cnpj: "11222333000181".parse().unwrap(),
+        });
+        assert!(builder().banking_billet(company, date()).build().is_ok());
+
+        let mut no_phone = customer();
+        no_phone.phone_number = None;
+        assert_eq!(
+            field(
+                builder()
+                    .credit_card(no_phone, "token", 1, address())
+                    .build()
+            ),
+            "customer.phone_number"
+        );
+        assert_eq!(
+            field(
+                builder()
+                    .credit_card(customer(), "token", 13, address())
+                    .build()
+            ),
+            "credit_card.installments"
+        );
+        assert_eq!(
+            field(
+                builder()
+                    .banking_billet(customer(), date())
+                    .billet_configurations(Some(1001), None)
+                    .build()
+            ),
+            "configurations.fine"
+        );
+        assert_eq!(
+            field(
+                builder()
+                    .banking_billet(customer(), date())
+                    .shipping("Frete", Brl::from_cents(0))
+                    .build()
+            ),
+            "shippings.value"
+        );
+    }
+
+    #[test]
+    fn carnet_checks_parcels_customer_and_metadata() {
+        let carnet =
+            |repeats| CarnetCreateRequest::builder(carnet_customer(), date(), repeats).item(item());
+        assert!(carnet(2).build().is_ok());
+        assert!(carnet(12).build().is_ok());
+        assert_eq!(field(carnet(1).build()), "repeats");
+        assert_eq!(field(carnet(13).build()), "repeats");
+
+        let empty = CarnetCreateRequest::builder(carnet_customer(), date(), 3);
+        assert_eq!(field(empty.build()), "items");
+        assert_eq!(field(carnet(3).message("x".repeat(81)).build()), "message");
+        assert_eq!(
+            field(carnet(3).custom_id(" ").build()),
+            "metadata.custom_id"
+        );
+        assert_eq!(
+            field(carnet(3).configurations(None, Some(331)).build()),
+            "configurations.interest"
+        );
+
+        let mut customer = carnet_customer();
+        customer.cpf = None;
+        let undocumented = CarnetCreateRequest::builder(customer.clone(), date(), 3).item(item());
+        assert_eq!(field(undocumented.build()), "customer.cpf");
+        customer.cnpj = Some("11222333000181".parse().unwrap());
+        let with_cnpj = CarnetCreateRequest::builder(customer, date(), 3).item(item());
+        assert!(with_cnpj.build().is_ok());
+    }
+}
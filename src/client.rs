@@ -1,20 +1,23 @@
-use std::sync::Mutex;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::{fs, path::PathBuf};
 
 use reqwest::{Client as HttpClient, Identity, Method, StatusCode};
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 
-use crate::auth::AccessToken;
-use crate::environment::{Endpoints, Environment};
+use crate::auth::TokenStore;
+use crate::environment::{Api, Endpoints, Environment};
 use crate::error::Error;
+use crate::retry::{AttemptOutcome, RetryAttempt, RetryPolicy};
 
 pub struct Client {
     pub(crate) id: String,
     pub(crate) secret: String,
-    pub(crate) environment: Environment,
+    pub(crate) endpoints: Endpoints,
     pub(crate) http: HttpClient,
-    pub(crate) token: Mutex<Option<AccessToken>>,
+    pub(crate) tokens: TokenStore,
+    pub(crate) retry_policy: RetryPolicy,
 }
 
 enum MtlsSource {
@@ -28,6 +31,7 @@ pub struct ClientBuilder {
     environment: Environment,
     http: Option<HttpClient>,
     mtls_source: Option<MtlsSource>,
+    retry_policy: RetryPolicy,
 }
 
 impl Default for ClientBuilder {
@@ -38,6 +42,7 @@ impl Default for ClientBuilder {
             environment: Environment::Sandbox,
             http: None,
             mtls_source: None,
+            retry_policy: RetryPolicy::default(),
         }
     }
 }
@@ -71,17 +76,29 @@ impl ClientBuilder {
     }
 
     #[must_use]
-    pub const fn environment(mut self, environment: Environment) -> Self {
+    pub fn environment(mut self, environment: Environment) -> Self {
         self.environment = environment;
         self
     }
 
+    #[must_use]
+    pub fn endpoints(mut self, endpoints: Endpoints) -> Self {
+        self.environment = Environment::Custom(endpoints);
+        self
+    }
+
     #[must_use]
     pub fn http_client(mut self, http_client: HttpClient) -> Self {
         self.http = Some(http_client);
         self
     }
 
+    #[must_use]
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
     #[must_use]
     pub fn pkcs12_der(mut self, der: impl Into<Vec<u8>>, password: impl Into<String>) -> Self {
         self.mtls_source = Some(MtlsSource::Pkcs12Der {
@@ -132,31 +149,34 @@ impl ClientBuilder {
         Ok(Client::from_parts(
             client_id,
             client_secret,
-            self.environment,
+            self.environment.endpoints(),
             http_client,
+            self.retry_policy,
         ))
     }
 }
 
 impl Client {
-    const fn from_parts(
+    fn from_parts(
         client_id: String,
         client_secret: String,
-        environment: Environment,
+        endpoints: Endpoints,
         http_client: HttpClient,
+        retry_policy: RetryPolicy,
     ) -> Self {
         Self {
             id: client_id,
             secret: client_secret,
-            environment,
+            endpoints,
             http: http_client,
-            token: Mutex::new(None),
+            tokens: Arc::new(Mutex::new(HashMap::new())),
+            retry_policy,
         }
     }
 
     #[must_use]
-    pub const fn endpoints(&self) -> Endpoints {
-        self.environment.endpoints()
+    pub const fn endpoints(&self) -> &Endpoints {
+        &self.endpoints
     }
 
     pub(crate) async fn send_authenticated<Req, Res>(
@@ -169,21 +189,8 @@ impl Client {
         Req: Serialize + Sync,
         Res: DeserializeOwned,
     {
-        let token = self.get_valid_access_token().await?;
-        let first_response = self
-            .send_with_token_typed::<Req>(&token, method.clone(), path, payload)
-            .await?;
-
-        if first_response.status() == StatusCode::UNAUTHORIZED {
-            self.authenticate().await?;
-            let refreshed_token = self.get_valid_access_token().await?;
-            let retry_response = self
-                .send_with_token_typed::<Req>(&refreshed_token, method, path, payload)
-                .await?;
-            return Self::parse_response::<Res>(retry_response).await;
-        }
-
-        Self::parse_response::<Res>(first_response).await
+        self.send_authenticated_api(Api::Pix, method, path, payload)
+            .await
     }
 
     pub(crate) async fn send_authenticated_billing<Req, Res>(
@@ -196,38 +203,59 @@ impl Client {
         Req: Serialize + Sync,
         Res: DeserializeOwned,
     {
-        let token = self.get_valid_billing_access_token().await?;
-        let first_response = self
-            .send_with_token_typed_base::<Req>(
-                &token,
-                self.endpoints().billing_api_base_url,
-                method.clone(),
-                path,
-                payload,
-            )
-            .await?;
+        self.send_authenticated_api(Api::Billing, method, path, payload)
+            .await
+    }
 
-        if first_response.status() == StatusCode::UNAUTHORIZED {
-            self.authenticate_billing().await?;
-            let refreshed_token = self.get_valid_billing_access_token().await?;
-            let retry_response = self
-                .send_with_token_typed_base::<Req>(
-                    &refreshed_token,
-                    self.endpoints().billing_api_base_url,
-                    method,
-                    path,
-                    payload,
-                )
-                .await?;
-            return Self::parse_response::<Res>(retry_response).await;
+    async fn send_authenticated_api<Req, Res>(
+        &self,
+        api: Api,
+        method: Method,
+        path: &str,
+        payload: Option<&Req>,
+    ) -> Result<Res, Error>
+    where
+        Req: Serialize + Sync,
+        Res: DeserializeOwned,
+    {
+        let retryable = self.retry_policy.applies_to(&method);
+        let mut attempt = 1;
+
+        loop {
+            let result = self
+                .send_authorized::<Req>(api, method.clone(), path, payload)
+                .await;
+
+            let retry_in = if retryable {
+                self.retry_policy.retry_delay(attempt, &result)
+            } else {
+                None
+            };
+
+            self.retry_policy.observe(&RetryAttempt {
+                method: &method,
+                path,
+                attempt,
+                outcome: match &result {
+                    Ok(response) => AttemptOutcome::Status(response.status()),
+                    Err(error) => AttemptOutcome::Error(error),
+                },
+                retry_in,
+            });
+
+            match retry_in {
+                Some(delay) => {
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                None => return Self::parse_response::<Res>(api, result?).await,
+            }
         }
-
-        Self::parse_response::<Res>(first_response).await
     }
 
-    async fn send_with_token_typed<Req>(
+    async fn send_authorized<Req>(
         &self,
-        access_token: &str,
+        api: Api,
         method: Method,
         path: &str,
         payload: Option<&Req>,
@@ -235,20 +263,26 @@ impl Client {
     where
         Req: Serialize + Sync,
     {
-        self.send_with_token_typed_base(
-            access_token,
-            self.endpoints().pix_api_base_url,
-            method,
-            path,
-            payload,
-        )
-        .await
+        let token = self.get_valid_access_token(api).await?;
+        let first_response = self
+            .send_with_token_typed::<Req>(&token, api, method.clone(), path, payload)
+            .await?;
+
+        if first_response.status() == StatusCode::UNAUTHORIZED {
+            self.invalidate_access_token(api, &token)?;
+            let refreshed_token = self.get_valid_access_token(api).await?;
+            return self
+                .send_with_token_typed::<Req>(&refreshed_token, api, method, path, payload)
+                .await;
+        }
+
+        Ok(first_response)
     }
 
-    async fn send_with_token_typed_base<Req>(
+    async fn send_with_token_typed<Req>(
         &self,
         access_token: &str,
-        base_url: &str,
+        api: Api,
         method: Method,
         path: &str,
         payload: Option<&Req>,
@@ -256,6 +290,7 @@ impl Client {
     where
         Req: Serialize + Sync,
     {
+        let base_url = self.endpoints().base_url(api);
         let url = format!("{base_url}{path}");
 
         let mut request = self.http.request(method, url).bearer_auth(access_token);
@@ -267,14 +302,14 @@ impl Client {
         Ok(request.send().await?)
     }
 
-    async fn parse_response<Res>(response: reqwest::Response) -> Result<Res, Error>
+    async fn parse_response<Res>(api: Api, response: reqwest::Response) -> Result<Res, Error>
     where
         Res: DeserializeOwned,
     {
         if !response.status().is_success() {
             let status = response.status();
             let body = response.text().await.unwrap_or_else(|_| String::new());
-            return Err(Error::RequestFailed { status, body });
+            return Err(Error::from_response(api, status, body));
         }
 
         let body = response.text().await?;
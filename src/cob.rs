@@ -1,8 +1,12 @@
+use futures_util::stream::BoxStream;
 use reqwest::Method;
 
+use crate::api::PixChargesApi;
 use crate::client::Client;
 use crate::error::Error;
-use crate::types::{CobPayload, CobResponse};
+use crate::pagination::with_query;
+use crate::txid::Txid;
+use crate::types::{CobListQuery, CobListResponse, CobPayload, CobResponse};
 
 impl Client {
     pub async fn cob_create(&self, payload: &CobPayload) -> Result<CobResponse, Error> {
@@ -10,49 +14,52 @@ impl Client {
             .await
     }
 
-    pub async fn cob_update(&self, txid: &str, payload: &CobPayload) -> Result<CobResponse, Error> {
+    /// Creates the charge under a txid you chose (`PUT /v2/cob/{txid}`), so a
+    /// retried request cannot create a second charge.
+    pub async fn cob_create_with_txid(
+        &self,
+        txid: &Txid,
+        payload: &CobPayload,
+    ) -> Result<CobResponse, Error> {
         let path = format!("/v2/cob/{txid}");
         self.send_authenticated(Method::PUT, &path, Some(payload))
             .await
     }
 
+    /// Same request as [`Client::cob_create_with_txid`]; fails before sending
+    /// if `txid` is not 26 to 35 letters or digits. Efí rejects a txid that is
+    /// already in use; revise an existing charge with [`Client::cob_patch`].
+    pub async fn cob_update(&self, txid: &str, payload: &CobPayload) -> Result<CobResponse, Error> {
+        self.cob_create_with_txid(&Txid::parse(txid)?, payload)
+            .await
+    }
+
     pub async fn cob_patch(&self, txid: &str, payload: &CobPayload) -> Result<CobResponse, Error> {
+        let txid = Txid::parse(txid)?;
         let path = format!("/v2/cob/{txid}");
         self.send_authenticated(Method::PATCH, &path, Some(payload))
             .await
     }
 
     pub async fn cob_get(&self, txid: &str) -> Result<CobResponse, Error> {
+        let txid = Txid::parse(txid)?;
         let path = format!("/v2/cob/{txid}");
         self.send_authenticated::<serde_json::Value, CobResponse>(Method::GET, &path, None)
             .await
     }
 
-    pub async fn cob_list(
-        &self,
-        cpf: Option<&str>,
-        status: Option<&str>,
-        limit: Option<i32>,
-    ) -> Result<Vec<CobResponse>, Error> {
-        let mut path = String::from("/v2/cob");
-        let mut params = Vec::new();
-
-        if let Some(c) = cpf {
-            params.push(format!("cpf={c}"));
-        }
-        if let Some(s) = status {
-            params.push(format!("status={s}"));
-        }
-        if let Some(l) = limit {
-            params.push(format!("limit={l}"));
-        }
-
-        if !params.is_empty() {
-            path.push('?');
-            path.push_str(&params.join("&"));
-        }
-
-        self.send_authenticated::<serde_json::Value, Vec<CobResponse>>(Method::GET, &path, None)
+    pub async fn cob_list(&self, query: &CobListQuery) -> Result<CobListResponse, Error> {
+        let path = with_query("/v2/cob", query)?;
+        self.send_authenticated::<serde_json::Value, CobListResponse>(Method::GET, &path, None)
             .await
     }
+
+    /// Every charge matching `query`, fetching one page at a time as the
+    /// stream is polled. Starts at `query.pagina_atual`, or the first page.
+    pub fn cob_list_stream(
+        &self,
+        query: CobListQuery,
+    ) -> BoxStream<'_, Result<CobResponse, Error>> {
+        PixChargesApi::cob_list_stream(self, query)
+    }
 }
@@ -1,8 +1,12 @@
+use futures_util::stream::BoxStream;
 use reqwest::Method;
 
+use crate::api::PixChargesApi;
 use crate::client::Client;
 use crate::error::Error;
-use crate::types::{CobvPayload, CobvResponse};
+use crate::pagination::with_query;
+use crate::txid::Txid;
+use crate::types::{CobListQuery, CobvListResponse, CobvPayload, CobvResponse};
 
 impl Client {
     pub async fn cobv_create(&self, payload: &CobvPayload) -> Result<CobvResponse, Error> {
@@ -10,11 +14,15 @@ impl Client {
             .await
     }
 
+    /// Creates the charge under `txid` (`PUT /v2/cobv/{txid}`). Efí rejects a
+    /// txid that is already in use; revise an existing charge with
+    /// [`Client::cobv_patch`].
     pub async fn cobv_update(
         &self,
         txid: &str,
         payload: &CobvPayload,
     ) -> Result<CobvResponse, Error> {
+        let txid = Txid::parse(txid)?;
         let path = format!("/v2/cobv/{txid}");
         self.send_authenticated(Method::PUT, &path, Some(payload))
             .await
@@ -25,42 +33,31 @@ impl Client {
         txid: &str,
         payload: &CobvPayload,
     ) -> Result<CobvResponse, Error> {
+        let txid = Txid::parse(txid)?;
         let path = format!("/v2/cobv/{txid}");
         self.send_authenticated(Method::PATCH, &path, Some(payload))
             .await
     }
 
     pub async fn cobv_get(&self, txid: &str) -> Result<CobvResponse, Error> {
+        let txid = Txid::parse(txid)?;
         let path = format!("/v2/cobv/{txid}");
         self.send_authenticated::<serde_json::Value, CobvResponse>(Method::GET, &path, None)
             .await
     }
 
-    pub async fn cobv_list(
-        &self,
-        cpf: Option<&str>,
-        status: Option<&str>,
-        limit: Option<i32>,
-    ) -> Result<Vec<CobvResponse>, Error> {
-        let mut path = String::from("/v2/cobv");
-        let mut params = Vec::new();
-
-        if let Some(c) = cpf {
-            params.push(format!("cpf={c}"));
-        }
-        if let Some(s) = status {
-            params.push(format!("status={s}"));
-        }
-        if let Some(l) = limit {
-            params.push(format!("limit={l}"));
-        }
-
-        if !params.is_empty() {
-            path.push('?');
-            path.push_str(&params.join("&"));
-        }
-
-        self.send_authenticated::<serde_json::Value, Vec<CobvResponse>>(Method::GET, &path, None)
+    pub async fn cobv_list(&self, query: &CobListQuery) -> Result<CobvListResponse, Error> {
+        let path = with_query("/v2/cobv", query)?;
+        self.send_authenticated::<serde_json::Value, CobvListResponse>(Method::GET, &path, None)
             .await
     }
+
+    /// Every charge matching `query`, fetching one page at a time as the
+    /// stream is polled. Starts at `query.pagina_atual`, or the first page.
+    pub fn cobv_list_stream(
+        &self,
+        query: CobListQuery,
+    ) -> BoxStream<'_, Result<CobvResponse, Error>> {
+        PixChargesApi::cobv_list_stream(self, query)
+    }
 }
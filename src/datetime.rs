@@ -0,0 +1,393 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::Error;
+
+/// Calendar date sent as `YYYY-MM-DD`, used for COBV due dates and Billing
+/// expiration dates.
+///
+/// With the `chrono` feature it converts to and from `chrono::NaiveDate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Builds a date, rejecting years past 9999 and days that do not exist.
+    pub fn new(year: u16, month: u8, day: u8) -> Result<Self, Error> {
+        if year > 9999 {
+            return Err(invalid_date("year must have at most four digits"));
+        }
+        if !(1..=12).contains(&month) {
+            return Err(invalid_date("month must be between 1 and 12"));
+        }
+        if day == 0 || day > days_in_month(year, month) {
+            return Err(invalid_date("day does not exist in that month"));
+        }
+        Ok(Self { year, month, day })
+    }
+
+    #[must_use]
+    pub const fn year(self) -> u16 {
+        self.year
+    }
+
+    #[must_use]
+    pub const fn month(self) -> u8 {
+        self.month
+    }
+
+    #[must_use]
+    pub const fn day(self) -> u8 {
+        self.day
+    }
+
+    /// The same day `months` later, moved back to the last day of the month
+    /// when that month is shorter.
+    pub(crate) fn add_months(self, months: u32) -> Result<Self, Error> {
+        let index = u32::from(self.month) - 1 + months;
+        let year = u16::try_from(u32::from(self.year) + index / 12)
+            .map_err(|_| invalid_date("year must have at most four digits"))?;
+        let month = u8::try_from(index % 12 + 1).expect("month index is below 12");
+        Self::new(year, month, self.day.min(days_in_month(year, month)))
+    }
+}
+
+const fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+const fn invalid_date(reason: &'static str) -> Error {
+    Error::InvalidValue {
+        kind: "date",
+        reason,
+    }
+}
+
+const fn invalid_timestamp(reason: &'static str) -> Error {
+    Error::InvalidValue {
+        kind: "timestamp",
+        reason,
+    }
+}
+
+/// Parses exactly `len` ASCII digits.
+fn digits(value: &str, len: usize) -> Option<u16> {
+    if value.len() != len || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let malformed = || invalid_date("expected YYYY-MM-DD");
+        let mut parts = value.split('-');
+        let (Some(year), Some(month), Some(day), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(malformed());
+        };
+        let year = digits(year, 4).ok_or_else(malformed)?;
+        let month = digits(month, 2).ok_or_else(malformed)?;
+        let day = digits(day, 2).ok_or_else(malformed)?;
+        Self::new(year, month as u8, day as u8)
+    }
+}
+
+impl Serialize for Date {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Date {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// RFC 3339 timestamp, as used by the Pix API (`2024-01-01T10:00:00.000Z`).
+///
+/// The original text is kept so the offset and precision Efí sent survive a
+/// round trip. A leap second (`23:59:60`) is accepted, as RFC 3339 allows. With the `chrono` feature it converts to and from
+/// `chrono::DateTime`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Timestamp(String);
+
+impl Timestamp {
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    #[must_use]
+    pub fn date(&self) -> Date {
+        self.0[..10]
+            .parse()
+            .expect("timestamp date was validated on construction")
+    }
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let malformed = || invalid_timestamp("expected an RFC 3339 date-time");
+        let in_range = |value: &str, max: u16| digits(value, 2).filter(|v| *v <= max);
+
+        let (date, rest) = value.split_at_checked(10).ok_or_else(malformed)?;
+        date.parse::<Date>()?;
+        let rest = rest.strip_prefix(['T', 't']).ok_or_else(malformed)?;
+        let (time, offset) = rest
+            .split_at_checked(8)
+            .filter(|(time, _)| time.is_ascii())
+            .ok_or_else(malformed)?;
+        if time.as_bytes()[2] != b':' || time.as_bytes()[5] != b':' {
+            return Err(malformed());
+        }
+        in_range(&time[0..2], 23).ok_or_else(malformed)?;
+        in_range(&time[3..5], 59).ok_or_else(malformed)?;
+        // RFC 3339 allows `60` here for a leap second.
+        in_range(&time[6..8], 60).ok_or_else(malformed)?;
+
+        let offset = match offset.strip_prefix('.') {
+            Some(fraction) => {
+                let end = fraction
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(fraction.len());
+                if end == 0 {
+                    return Err(malformed());
+                }
+                &fraction[end..]
+            }
+            None => offset,
+        };
+        match offset.as_bytes() {
+            [b'Z' | b'z'] => {}
+            [b'+' | b'-', _, _, b':', _, _] => {
+                in_range(&offset[1..3], 23).ok_or_else(malformed)?;
+                in_range(&offset[4..6], 59).ok_or_else(malformed)?;
+            }
+            _ => return Err(malformed()),
+        }
+
+        Ok(Self(value.to_owned()))
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, SecondsFormat, TimeZone};
+
+    use super::{Date, Timestamp, invalid_date, invalid_timestamp};
+    use crate::error::Error;
+
+    impl Date {
+        #[must_use]
+        pub fn to_naive_date(self) -> NaiveDate {
+            NaiveDate::from_ymd_opt(
+                i32::from(self.year),
+                u32::from(self.month),
+                u32::from(self.day),
+            )
+            .expect("date was validated on construction")
+        }
+    }
+
+    impl From<Date> for NaiveDate {
+        fn from(date: Date) -> Self {
+            date.to_naive_date()
+        }
+    }
+
+    impl TryFrom<NaiveDate> for Date {
+        type Error = Error;
+
+        fn try_from(date: NaiveDate) -> Result<Self, Self::Error> {
+            let year = u16::try_from(date.year())
+                .map_err(|_| invalid_date("year must have at most four digits"))?;
+            Self::new(year, date.month() as u8, date.day() as u8)
+        }
+    }
+
+    impl Timestamp {
+        #[must_use]
+        pub fn to_datetime(&self) -> DateTime<FixedOffset> {
+            DateTime::parse_from_rfc3339(&self.0).expect("timestamp was validated on construction")
+        }
+    }
+
+    impl From<&Timestamp> for DateTime<FixedOffset> {
+        fn from(timestamp: &Timestamp) -> Self {
+            timestamp.to_datetime()
+        }
+    }
+
+    impl<Tz: TimeZone> TryFrom<DateTime<Tz>> for Timestamp
+    where
+        Tz::Offset: std::fmt::Display,
+    {
+        type Error = Error;
+
+        fn try_from(datetime: DateTime<Tz>) -> Result<Self, Self::Error> {
+            if !(0..=9999).contains(&datetime.year()) {
+                return Err(invalid_timestamp("year must have at most four digits"));
+            }
+            Ok(Self(datetime.to_rfc3339_opts(SecondsFormat::AutoSi, true)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Date, Timestamp};
+
+    fn date(value: &str) -> Date {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_timestamps() {
+        for value in [
+            "2024-01-01T10:00:00Z",
+            "2024-01-01t10:00:00z",
+            "2024-01-01T10:00:00-03:00",
+            "2024-01-01T10:00:00+05:30",
+            "2024-01-01T10:00:00.1Z",
+            "2024-01-01T10:00:00.123456789-03:00",
+            "2016-12-31T23:59:60Z",
+        ] {
+            let timestamp: Timestamp = value.parse().unwrap();
+            assert_eq!(timestamp.to_string(), value);
+        }
+    }
+
+    #[test]
+    fn rejects_bad_timestamps() {
+        for value in [
+            "",
+            "2024-01-01",
+            "2024-02-30T10:00:00Z",
+            "2023-02-29T10:00:00Z",
+            "2024-01-01 10:00:00Z",
+            "2024-01-01T24:00:00Z",
+            "2024-01-01T10:60:00Z",
+            "2024-01-01T10:00:61Z",
+            "2024-01-01T10-00-00Z",
+            "2024-01-01T10:00:00",
+            "2024-01-01T10:00:00.Z",
+            "2024-01-01T10:00:00.5",
+            "2024-01-01T10:00:00+0300",
+            "2024-01-01T10:00:00+24:00",
+            "2024-01-01T10:00:00Z ",
+            "2024-01-01T10:00:0é",
+        ] {
+            assert!(value.parse::<Timestamp>().is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn timestamp_round_trips_through_json() {
+        let json = r#""2024-03-10T08:15:30.250-03:00""#;
+        let timestamp: Timestamp = serde_json::from_str(json).unwrap();
+        assert_eq!(serde_json::to_string(&timestamp).unwrap(), json);
+        assert_eq!(timestamp.date(), date("2024-03-10"));
+        assert!(serde_json::from_str::<Timestamp>(r#""2024-03-10""#).is_err());
+    }
+
+    #[test]
+    fn parses_dates() {
+        assert_eq!(date("2024-02-29"), Date::new(2024, 2, 29).unwrap());
+        assert_eq!(date("2000-02-29").to_string(), "2000-02-29");
+        assert_eq!(date("0001-01-01").year(), 1);
+        assert_eq!(
+            serde_json::to_string(&date("2024-12-31")).unwrap(),
+            r#""2024-12-31""#
+        );
+    }
+
+    #[test]
+    fn rejects_bad_dates() {
+        for value in [
+            "2023-02-29",
+            "1900-02-29",
+            "2024-04-31",
+            "2024-13-01",
+            "2024-00-10",
+            "2024-01-00",
+            "2024-1-01",
+            "24-01-01",
+            "2024/01/01",
+            "+024-01-01",
+            "2024-01-01-",
+        ] {
+            assert!(value.parse::<Date>().is_err(), "{value}");
+        }
+        assert!(Date::new(10_000, 1, 1).is_err());
+    }
+
+    #[test]
+    fn add_months_clamps_to_month_end() {
+        let cases = [
+            ("2024-01-31", 1, "2024-02-29"),
+            ("2023-01-31", 1, "2023-02-28"),
+            ("2024-03-31", 1, "2024-04-30"),
+            ("2024-01-31", 2, "2024-03-31"),
+            ("2024-11-15", 2, "2025-01-15"),
+            ("2024-01-31", 12, "2025-01-31"),
+            ("2024-02-29", 12, "2025-02-28"),
+            ("2024-05-20", 0, "2024-05-20"),
+        ];
+        for (start, months, expected) in cases {
+            assert_eq!(date(start).add_months(months).unwrap(), date(expected));
+        }
+        assert!(date("9999-12-01").add_months(1).is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn leap_second_converts_to_chrono() {
+        let timestamp: Timestamp = "2016-12-31T23:59:60Z".parse().unwrap();
+        assert_eq!(timestamp.to_datetime().timestamp(), 1_483_228_799);
+    }
+}
@@ -0,0 +1,295 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::Error;
+
+/// Brazilian individual taxpayer number.
+///
+/// Parses `12345678909` or `123.456.789-09`, validates the check digits and
+/// serializes the digits only. `Debug` masks all but the middle digits.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Cpf(String);
+
+/// Brazilian company taxpayer number, numeric or alphanumeric.
+///
+/// Parses `11222333000181`, `11.222.333/0001-81` or the alphanumeric form
+/// (`12.ABC.345/01DE-35`), validates the check digits and serializes without
+/// punctuation. `Debug` masks all but the middle characters.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Cnpj(String);
+
+impl Cpf {
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        let invalid = |reason| Error::InvalidValue {
+            kind: "cpf",
+            reason,
+        };
+        let digits = strip_punctuation(value)
+            .filter(|digits| digits.len() == 11 && digits.bytes().all(|b| b.is_ascii_digit()))
+            .ok_or_else(|| invalid("expected 11 digits"))?;
+        if !check_digits_match(&digits, &[11, 10, 9, 8, 7, 6, 5, 4, 3, 2]) {
+            return Err(invalid("check digits do not match"));
+        }
+        Ok(Self(digits))
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// `123.456.789-09`.
+    #[must_use]
+    pub fn formatted(&self) -> String {
+        let d = &self.0;
+        format!("{}.{}.{}-{}", &d[..3], &d[3..6], &d[6..9], &d[9..])
+    }
+}
+
+impl Cnpj {
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        let invalid = |reason| Error::InvalidValue {
+            kind: "cnpj",
+            reason,
+        };
+        let chars = strip_punctuation(value)
+            .map(|chars| chars.to_ascii_uppercase())
+            .filter(|chars| {
+                chars.len() == 14
+                    && chars[..12].bytes().all(|b| b.is_ascii_alphanumeric())
+                    && chars[12..].bytes().all(|b| b.is_ascii_digit())
+            })
+            .ok_or_else(|| invalid("expected 12 letters or digits followed by 2 digits"))?;
+        if !check_digits_match(&chars, &[6, 5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2]) {
+            return Err(invalid("check digits do not match"));
+        }
+        Ok(Self(chars))
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether the CNPJ uses letters, a format issued from July 2026 on.
+    #[must_use]
+    pub fn is_alphanumeric(&self) -> bool {
+        !self.0.bytes().all(|b| b.is_ascii_digit())
+    }
+
+    /// `11.222.333/0001-81`.
+    #[must_use]
+    pub fn formatted(&self) -> String {
+        let c = &self.0;
+        format!(
+            "{}.{}.{}/{}-{}",
+            &c[..2],
+            &c[2..5],
+            &c[5..8],
+            &c[8..12],
+            &c[12..]
+        )
+    }
+}
+
+/// Removes `.`, `-`, `/` and whitespace. `None` if anything left is not ASCII
+/// alphanumeric, so later slicing stays on character boundaries.
+pub(crate) fn strip_punctuation(value: &str) -> Option<String> {
+    let stripped: String = value
+        .chars()
+        .filter(|c| !matches!(c, '.' | '-' | '/') && !c.is_whitespace())
+        .collect();
+    stripped
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric())
+        .then_some(stripped)
+}
+
+/// Checks the two trailing mod-11 check digits. Each character is worth its
+/// ASCII code minus 48, which covers both numeric and alphanumeric CNPJs.
+/// `weights` are those of the second check digit; the first one uses the same
+/// list minus its head.
+fn check_digits_match(value: &str, weights: &[u32]) -> bool {
+    let values: Vec<u32> = value.bytes().map(|b| u32::from(b - b'0')).collect();
+    if values.iter().all(|&v| v == values[0]) {
+        return false;
+    }
+
+    let check_digit = |base: &[u32], weights: &[u32]| {
+        let sum: u32 = base
+            .iter()
+            .zip(weights)
+            .map(|(value, weight)| value * weight)
+            .sum();
+        match sum % 11 {
+            0 | 1 => 0,
+            remainder => 11 - remainder,
+        }
+    };
+    let len = values.len();
+    values[len - 2] == check_digit(&values[..len - 2], &weights[1..])
+        && values[len - 1] == check_digit(&values[..len - 1], weights)
+}
+
+impl Debug for Cpf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Cpf(***.{}.{}-**)", &self.0[3..6], &self.0[6..9])
+    }
+}
+
+impl Debug for Cnpj {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Cnpj(**.{}.{}/****-**)", &self.0[2..5], &self.0[5..8])
+    }
+}
+
+impl Display for Cpf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Display for Cnpj {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for Cpf {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+impl FromStr for Cnpj {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+impl Serialize for Cpf {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl Serialize for Cnpj {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Cpf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for Cnpj {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cnpj, Cpf};
+
+    #[test]
+    fn accepts_valid_cpfs() {
+        for value in [
+            "123.456.789-09",
+            "12345678909",
+            "529.982.247-25",
+            " 529 982 247 25 ",
+        ] {
+            assert!(Cpf::parse(value).is_ok(), "{value:?} should be valid");
+        }
+        let cpf = Cpf::parse("529.982.247-25").unwrap();
+        assert_eq!(cpf.as_str(), "52998224725");
+        assert_eq!(cpf.formatted(), "529.982.247-25");
+    }
+
+    #[test]
+    fn rejects_invalid_cpfs() {
+        for value in [
+            "123.456.789-00",
+            "123.456.789-19",
+            "529.982.247-52",
+            "111.111.111-11",
+            "000.000.000-00",
+            "1234567890",
+            "123456789091",
+            "123.456.789-0A",
+            "123.456.789_09",
+            "",
+        ] {
+            assert!(Cpf::parse(value).is_err(), "{value:?} should be invalid");
+        }
+    }
+
+    #[test]
+    fn accepts_valid_numeric_cnpjs() {
+        let cnpj = Cnpj::parse("11.222.333/0001-81").unwrap();
+        assert_eq!(cnpj.as_str(), "11222333000181");
+        assert_eq!(cnpj.formatted(), "11.222.333/0001-81");
+        assert!(!cnpj.is_alphanumeric());
+        assert!(Cnpj::parse("11222333000181").is_ok());
+    }
+
+    #[test]
+    fn accepts_valid_alphanumeric_cnpjs() {
+        let cnpj = Cnpj::parse("12.ABC.345/01DE-35").unwrap();
+        assert_eq!(cnpj.as_str(), "12ABC34501DE35");
+        assert_eq!(cnpj.formatted(), "12.ABC.345/01DE-35");
+        assert!(cnpj.is_alphanumeric());
+        assert_eq!(Cnpj::parse("12abc34501de35").unwrap(), cnpj);
+    }
+
+    #[test]
+    fn rejects_invalid_cnpjs() {
+        for value in [
+            "11.222.333/0001-82",
+            "11.222.333/0001-18",
+            "12.ABC.345/01DE-36",
+            "12.ABC.345/01DF-35",
+            "12.ABC.345/01DE-3A",
+            "00.000.000/0000-00",
+            "1122233300018",
+            "112223330001811",
+            "12.ABÇ.345/01DE-35",
+            "",
+        ] {
+            assert!(Cnpj::parse(value).is_err(), "{value:?} should be invalid");
+        }
+    }
+
+    #[test]
+    fn debug_masks_the_document() {
+        assert_eq!(
+            format!("{:?}", Cpf::parse("123.456.789-09").unwrap()),
+            "Cpf(***.456.789-**)"
+        );
+        assert_eq!(
+            format!("{:?}", Cnpj::parse("11.222.333/0001-81").unwrap()),
+            "Cnpj(**.222.333/****-**)"
+        );
+    }
+
+    #[test]
+    fn serializes_without_punctuation() {
+        let cnpj = Cnpj::parse("12.ABC.345/01DE-35").unwrap();
+        assert_eq!(serde_json::to_string(&cnpj).unwrap(), r#""12ABC34501DE35""#);
+        let cpf: Cpf = serde_json::from_str(r#""123.456.789-09""#).unwrap();
+        assert_eq!(cpf.as_str(), "12345678909");
+        assert!(serde_json::from_str::<Cpf>(r#""123.456.789-00""#).is_err());
+    }
+}
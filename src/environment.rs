@@ -1,37 +1,84 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use std::borrow::Cow;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Environment {
     Sandbox,
     Production,
+    /// Any other set of hosts, e.g. a local mock server or an egress proxy.
+    Custom(Endpoints),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Endpoints {
-    pub pix_api_base_url: &'static str,
-    pub pix_api_oauth_token_url: &'static str,
-    pub billing_api_base_url: &'static str,
-    pub billing_api_oauth_token_url: &'static str,
+    pub pix_api_base_url: Cow<'static, str>,
+    pub pix_api_oauth_token_url: Cow<'static, str>,
+    pub billing_api_base_url: Cow<'static, str>,
+    pub billing_api_oauth_token_url: Cow<'static, str>,
 }
 
 pub const SANDBOX_ENDPOINTS: Endpoints = Endpoints {
-    pix_api_base_url: "https://pix-h.api.efipay.com.br",
-    pix_api_oauth_token_url: "https://pix-h.api.efipay.com.br/oauth/token",
-    billing_api_base_url: "https://cobrancas-h.api.efipay.com.br",
-    billing_api_oauth_token_url: "https://cobrancas-h.api.efipay.com.br/v1/authorize",
+    pix_api_base_url: Cow::Borrowed("https://pix-h.api.efipay.com.br"),
+    pix_api_oauth_token_url: Cow::Borrowed("https://pix-h.api.efipay.com.br/oauth/token"),
+    billing_api_base_url: Cow::Borrowed("https://cobrancas-h.api.efipay.com.br"),
+    billing_api_oauth_token_url: Cow::Borrowed(
+        "https://cobrancas-h.api.efipay.com.br/v1/authorize",
+    ),
 };
 
 pub const PRODUCTION_ENDPOINTS: Endpoints = Endpoints {
-    pix_api_base_url: "https://pix.api.efipay.com.br",
-    pix_api_oauth_token_url: "https://pix.api.efipay.com.br/oauth/token",
-    billing_api_base_url: "https://cobrancas.api.efipay.com.br",
-    billing_api_oauth_token_url: "https://cobrancas.api.efipay.com.br/v1/authorize",
+    pix_api_base_url: Cow::Borrowed("https://pix.api.efipay.com.br"),
+    pix_api_oauth_token_url: Cow::Borrowed("https://pix.api.efipay.com.br/oauth/token"),
+    billing_api_base_url: Cow::Borrowed("https://cobrancas.api.efipay.com.br"),
+    billing_api_oauth_token_url: Cow::Borrowed("https://cobrancas.api.efipay.com.br/v1/authorize"),
 };
 
 impl Environment {
     #[must_use]
-    pub const fn endpoints(self) -> Endpoints {
+    pub fn endpoints(&self) -> Endpoints {
         match self {
             Self::Sandbox => SANDBOX_ENDPOINTS,
             Self::Production => PRODUCTION_ENDPOINTS,
+            Self::Custom(endpoints) => endpoints.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Api {
+    Pix,
+    Billing,
+}
+
+impl Endpoints {
+    /// Builds endpoints for hosts that mirror the Efí paths, deriving the OAuth
+    /// URLs (`/oauth/token` and `/v1/authorize`) from the two base URLs.
+    #[must_use]
+    pub fn new(
+        pix_api_base_url: impl Into<String>,
+        billing_api_base_url: impl Into<String>,
+    ) -> Self {
+        let pix_api_base_url = pix_api_base_url.into().trim_end_matches('/').to_owned();
+        let billing_api_base_url = billing_api_base_url.into().trim_end_matches('/').to_owned();
+
+        Self {
+            pix_api_oauth_token_url: Cow::Owned(format!("{pix_api_base_url}/oauth/token")),
+            pix_api_base_url: Cow::Owned(pix_api_base_url),
+            billing_api_oauth_token_url: Cow::Owned(format!("{billing_api_base_url}/v1/authorize")),
+            billing_api_base_url: Cow::Owned(billing_api_base_url),
+        }
+    }
+
+    pub(crate) fn base_url(&self, api: Api) -> &str {
+        match api {
+            Api::Pix => &self.pix_api_base_url,
+            Api::Billing => &self.billing_api_base_url,
+        }
+    }
+
+    pub(crate) fn oauth_token_url(&self, api: Api) -> &str {
+        match api {
+            Api::Pix => &self.pix_api_oauth_token_url,
+            Api::Billing => &self.billing_api_oauth_token_url,
         }
     }
 }
@@ -1,20 +1,241 @@
 use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use crate::brcode::BrCodeError;
+use crate::environment::Api;
 
 #[derive(Debug)]
 pub enum Error {
     BuilderMissingField(&'static str),
     BuilderConflict(&'static str),
+    BuilderInvalidField {
+        field: &'static str,
+        reason: &'static str,
+    },
+    InvalidValue {
+        kind: &'static str,
+        reason: &'static str,
+    },
     Io(std::io::Error),
     Http(reqwest::Error),
     Json(serde_json::Error),
+    BrCode(BrCodeError),
     AuthUnavailable,
+    AuthFailed(Arc<Error>),
     EmptyResponse,
+    /// Problem document (RFC 7807) returned by the Pix API.
+    Pix {
+        status: StatusCode,
+        problem: Box<PixProblem>,
+        body: String,
+    },
+    /// `{code, error, error_description}` document returned by the Billing API.
+    Billing {
+        status: StatusCode,
+        problem: Box<BillingProblem>,
+        body: String,
+    },
+    /// Non-2xx response whose body is not a recognizable error document.
     RequestFailed {
-        status: reqwest::StatusCode,
+        status: StatusCode,
         body: String,
     },
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    Validation,
+    NotFound,
+    Conflict,
+    InsufficientBalance,
+    RateLimited,
+    Unauthorized,
+    Server,
+    Other,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PixProblem {
+    #[serde(rename = "type")]
+    pub r#type: Option<String>,
+    pub title: Option<String>,
+    pub status: Option<u16>,
+    pub detail: Option<String>,
+    #[serde(default)]
+    pub violacoes: Vec<PixViolacao>,
+    /// Error name used by the `/v2/gn` endpoints instead of `type`, e.g. `saldo_insuficiente`.
+    pub nome: Option<String>,
+    /// Human readable message used by the `/v2/gn` endpoints instead of `detail`.
+    pub mensagem: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PixViolacao {
+    pub razao: Option<String>,
+    pub propriedade: Option<String>,
+    pub valor: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillingProblem {
+    pub code: Option<i64>,
+    pub error: Option<String>,
+    pub error_description: Option<BillingErrorDescription>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BillingErrorDescription {
+    Message(String),
+    Property { property: String, message: String },
+    Other(serde_json::Value),
+}
+
+impl Error {
+    pub(crate) fn from_response(api: Api, status: StatusCode, body: String) -> Self {
+        match api {
+            Api::Pix => match serde_json::from_str::<PixProblem>(&body) {
+                Ok(problem) if problem.is_recognized() => Self::Pix {
+                    status,
+                    problem: Box::new(problem),
+                    body,
+                },
+                _ => Self::RequestFailed { status, body },
+            },
+            Api::Billing => match serde_json::from_str::<BillingProblem>(&body) {
+                Ok(problem) if problem.is_recognized() => Self::Billing {
+                    status,
+                    problem: Box::new(problem),
+                    body,
+                },
+                _ => Self::RequestFailed { status, body },
+            },
+        }
+    }
+
+    /// HTTP status of a failed request, if the error came from the server.
+    #[must_use]
+    pub const fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Pix { status, .. }
+            | Self::Billing { status, .. }
+            | Self::RequestFailed { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Raw response body of a failed request.
+    #[must_use]
+    pub fn body(&self) -> Option<&str> {
+        match self {
+            Self::Pix { body, .. }
+            | Self::Billing { body, .. }
+            | Self::RequestFailed { body, .. } => Some(body),
+            _ => None,
+        }
+    }
+
+    /// Machine-readable classification of a failed request.
+    #[must_use]
+    pub fn kind(&self) -> Option<ErrorKind> {
+        match self {
+            Self::Pix {
+                status, problem, ..
+            } => Some(ErrorKind::classify(
+                *status,
+                problem.nome.as_deref().or(problem.r#type.as_deref()),
+            )),
+            Self::Billing {
+                status, problem, ..
+            } => Some(ErrorKind::classify(*status, problem.error.as_deref())),
+            Self::RequestFailed { status, .. } => Some(ErrorKind::classify(*status, None)),
+            Self::AuthFailed(error) => error.kind(),
+            _ => None,
+        }
+    }
+}
+
+impl ErrorKind {
+    fn classify(status: StatusCode, name: Option<&str>) -> Self {
+        let name = name.unwrap_or_default().to_ascii_lowercase();
+
+        if name.contains("saldo") || name.contains("insufficient") {
+            return Self::InsufficientBalance;
+        }
+        if name.contains("naoencontrad") || name.contains("nao_encontrad") {
+            return Self::NotFound;
+        }
+
+        match status {
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited,
+            StatusCode::NOT_FOUND => Self::NotFound,
+            StatusCode::CONFLICT => Self::Conflict,
+            StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => Self::Validation,
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Self::Unauthorized,
+            status if status.is_server_error() => Self::Server,
+            _ => Self::Other,
+        }
+    }
+}
+
+impl PixProblem {
+    const fn is_recognized(&self) -> bool {
+        self.r#type.is_some() || self.title.is_some() || self.nome.is_some()
+    }
+}
+
+impl BillingProblem {
+    const fn is_recognized(&self) -> bool {
+        self.code.is_some() || self.error.is_some()
+    }
+}
+
+impl Display for PixProblem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let title = self
+            .title
+            .as_deref()
+            .or(self.nome.as_deref())
+            .unwrap_or("unknown error");
+        write!(f, "{title}")?;
+        if let Some(detail) = self.detail.as_deref().or(self.mensagem.as_deref()) {
+            write!(f, ": {detail}")?;
+        }
+        for violacao in &self.violacoes {
+            let propriedade = violacao.propriedade.as_deref().unwrap_or("?");
+            let razao = violacao.razao.as_deref().unwrap_or_default();
+            write!(f, " [{propriedade}: {razao}]")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for BillingProblem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error.as_deref().unwrap_or("unknown error"))?;
+        if let Some(code) = self.code {
+            write!(f, " ({code})")?;
+        }
+        match &self.error_description {
+            Some(BillingErrorDescription::Message(message)) => write!(f, ": {message}"),
+            Some(BillingErrorDescription::Property { property, message }) => {
+                write!(f, ": {property}: {message}")
+            }
+            Some(BillingErrorDescription::Other(value)) => write!(f, ": {value}"),
+            None => Ok(()),
+        }
+    }
+}
+
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -22,11 +243,23 @@ impl Display for Error {
                 write!(f, "missing required builder field: {field}")
             }
             Self::BuilderConflict(message) => write!(f, "invalid builder configuration: {message}"),
+            Self::BuilderInvalidField { field, reason } => {
+                write!(f, "invalid builder field {field}: {reason}")
+            }
+            Self::InvalidValue { kind, reason } => write!(f, "invalid {kind}: {reason}"),
             Self::Io(error) => write!(f, "io error: {error}"),
             Self::Http(error) => write!(f, "http error: {error}"),
             Self::Json(error) => write!(f, "json error: {error}"),
+            Self::BrCode(error) => write!(f, "{error}"),
             Self::AuthUnavailable => write!(f, "authentication token is unavailable"),
+            Self::AuthFailed(error) => write!(f, "authentication failed: {error}"),
             Self::EmptyResponse => write!(f, "received empty response from server"),
+            Self::Pix {
+                status, problem, ..
+            } => write!(f, "pix request failed with status {status}: {problem}"),
+            Self::Billing {
+                status, problem, ..
+            } => write!(f, "billing request failed with status {status}: {problem}"),
             Self::RequestFailed { status, body } => {
                 write!(f, "request failed with status {status}: {body}")
             }
@@ -34,6 +267,12 @@ impl Display for Error {
     }
 }
 
+impl From<BrCodeError> for Error {
+    fn from(value: BrCodeError) -> Self {
+        Self::BrCode(value)
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(value: std::io::Error) -> Self {
         Self::Io(value)
@@ -58,7 +297,170 @@ impl std::error::Error for Error {
             Self::Io(error) => Some(error),
             Self::Http(error) => Some(error),
             Self::Json(error) => Some(error),
+            Self::BrCode(error) => Some(error),
+            Self::AuthFailed(error) => Some(error.as_ref()),
             _ => None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use reqwest::StatusCode;
+
+    use super::{Error, ErrorKind};
+    use crate::environment::Api;
+
+    fn kind(api: Api, status: u16, body: &str) -> Option<ErrorKind> {
+        let status = StatusCode::from_u16(status).unwrap();
+        Error::from_response(api, status, body.to_owned()).kind()
+    }
+
+    #[test]
+    fn classifies_pix_problems() {
+        let cases = [
+            (
+                400,
+                r#"{"type":"https://pix.bcb.gov.br/api/v2/error/CobOperacaoInvalida","title":"Cobrança inválida.","status":400,"violacoes":[{"razao":"O campo cob.valor.original não respeita o schema.","propriedade":"cob.valor.original"}]}"#,
+                ErrorKind::Validation,
+            ),
+            (
+                404,
+                r#"{"type":"https://pix.bcb.gov.br/api/v2/error/CobNaoEncontrado","title":"Não Encontrado","status":404}"#,
+                ErrorKind::NotFound,
+            ),
+            (
+                409,
+                r#"{"type":"https://pix.bcb.gov.br/api/v2/error/CobOperacaoInvalida","title":"txid em uso","status":409}"#,
+                ErrorKind::Conflict,
+            ),
+            (
+                400,
+                r#"{"nome":"saldo_insuficiente","mensagem":"Saldo insuficiente para realizar o Pix"}"#,
+                ErrorKind::InsufficientBalance,
+            ),
+            (
+                400,
+                r#"{"nome":"chave_nao_encontrada","mensagem":"Chave Pix não encontrada"}"#,
+                ErrorKind::NotFound,
+            ),
+            (
+                422,
+                r#"{"nome":"PixNaoEncontrado","mensagem":"Pix não encontrado"}"#,
+                ErrorKind::NotFound,
+            ),
+            (
+                429,
+                r#"{"title":"Too Many Requests","status":429}"#,
+                ErrorKind::RateLimited,
+            ),
+            (
+                403,
+                r#"{"nome":"acesso_negado","mensagem":"Escopo insuficiente"}"#,
+                ErrorKind::Unauthorized,
+            ),
+            (
+                502,
+                r#"{"title":"Bad Gateway","status":502}"#,
+                ErrorKind::Server,
+            ),
+            (
+                418,
+                r#"{"title":"I'm a teapot","status":418}"#,
+                ErrorKind::Other,
+            ),
+        ];
+
+        for (status, body, expected) in cases {
+            assert_eq!(kind(Api::Pix, status, body), Some(expected), "{body}");
+        }
+    }
+
+    #[test]
+    fn classifies_billing_problems() {
+        let cases = [
+            (
+                400,
+                r#"{"code":3500034,"error":"validation_error","error_description":{"property":"/items/0/value","message":"O campo value é obrigatório."}}"#,
+                ErrorKind::Validation,
+            ),
+            (
+                404,
+                r#"{"code":3500010,"error":"charge_not_found","error_description":"Cobrança não encontrada."}"#,
+                ErrorKind::NotFound,
+            ),
+            (
+                400,
+                r#"{"code":3500034,"error":"insufficient_funds","error_description":"Saldo insuficiente."}"#,
+                ErrorKind::InsufficientBalance,
+            ),
+            (
+                401,
+                r#"{"code":401,"error":"unauthorized","error_description":"Token inválido."}"#,
+                ErrorKind::Unauthorized,
+            ),
+            (
+                500,
+                r#"{"code":500,"error":"server_error","error_description":{"unexpected":true}}"#,
+                ErrorKind::Server,
+            ),
+        ];
+
+        for (status, body, expected) in cases {
+            assert_eq!(kind(Api::Billing, status, body), Some(expected), "{body}");
+        }
+    }
+
+    #[test]
+    fn name_rules_are_substring_and_case_insensitive() {
+        assert_eq!(
+            ErrorKind::classify(StatusCode::CONFLICT, Some("SALDO_Bloqueado")),
+            ErrorKind::InsufficientBalance
+        );
+        assert_eq!(
+            ErrorKind::classify(StatusCode::BAD_REQUEST, Some("contaNaoEncontrada")),
+            ErrorKind::NotFound
+        );
+        assert_eq!(
+            ErrorKind::classify(StatusCode::BAD_REQUEST, Some("valor_invalido")),
+            ErrorKind::Validation
+        );
+        assert_eq!(
+            ErrorKind::classify(StatusCode::UNPROCESSABLE_ENTITY, None),
+            ErrorKind::Validation
+        );
+    }
+
+    #[test]
+    fn unrecognized_bodies_keep_status_and_body() {
+        let error = Error::from_response(
+            Api::Pix,
+            StatusCode::SERVICE_UNAVAILABLE,
+            "<html>down</html>".to_owned(),
+        );
+        assert!(matches!(error, Error::RequestFailed { .. }));
+        assert_eq!(error.status(), Some(StatusCode::SERVICE_UNAVAILABLE));
+        assert_eq!(error.body(), Some("<html>down</html>"));
+        assert_eq!(error.kind(), Some(ErrorKind::Server));
+
+        let error = Error::from_response(Api::Billing, StatusCode::NOT_FOUND, "{}".to_owned());
+        assert!(matches!(error, Error::RequestFailed { .. }));
+        assert_eq!(error.kind(), Some(ErrorKind::NotFound));
+    }
+
+    #[test]
+    fn auth_failures_forward_the_token_error() {
+        let rejected = Error::from_response(
+            Api::Pix,
+            StatusCode::UNAUTHORIZED,
+            r#"{"error":"invalid_client"}"#.to_owned(),
+        );
+        let error = Error::AuthFailed(Arc::new(rejected));
+        assert_eq!(error.kind(), Some(ErrorKind::Unauthorized));
+        assert_eq!(error.status(), None);
+        assert_eq!(error.body(), None);
+        assert_eq!(Error::EmptyResponse.kind(), None);
+    }
+}
@@ -0,0 +1,1639 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, MutexGuard};
+
+use async_trait::async_trait;
+use reqwest::StatusCode;
+use serde_json::json;
+
+use crate::api::{BillingApi, CarnetApi, PixChargesApi, PixTransfersApi, SplitApi, WebhooksApi};
+use crate::datetime::{Date, Timestamp};
+use crate::environment::Api;
+use crate::error::Error;
+use crate::money::Brl;
+use crate::pix::check_id;
+use crate::status::{
+    BillingChargeStatus, CarnetStatus, CobStatus, DevolucaoStatus, PixTransferStatus,
+};
+use crate::txid::Txid;
+use crate::types::{
+    BillingChargeCreateRequest, BillingChargeData, BillingChargeDetailBankingBillet,
+    BillingChargeDetailData, BillingChargeDetailPayment, BillingChargeDetailResponse,
+    BillingChargeHistoryEntry, BillingChargeOneStepRequest, BillingChargePayRequest,
+    BillingChargeResponse, BillingChargeShipping, BillingCustomer, BillingItem,
+    BillingItemResponse, BillingNotificationResponse, BillingPdf, BillingPix, BillingShipping,
+    CarnetCharge, CarnetChargePdf, CarnetCreateRequest, CarnetData, CarnetHistoryRequest,
+    CarnetMetadataRequest, CarnetParcelRequest, CarnetParcelsRequest, CarnetPdf, CarnetResponse,
+    CobCalendarioResponse, CobListQuery, CobListResponse, CobLoc, CobPayload, CobResponse,
+    CobValor, CobvCalendarioResponse, CobvListResponse, CobvPayload, CobvResponse, ComponenteValor,
+    ComponentesValor, DevedorResponse, Devolucao, DevolucaoHorario, DevolucaoPayload,
+    ListParametros, NaturezaDevolucao, Paginacao, PixEnviadoContaBanco, PixEnviadoFavorecido,
+    PixEnviadoListQuery, PixEnviadoListResponse, PixEnviadoPagador, PixEnvioHorario, PixFavorecido,
+    PixGnExtras, PixIdentificacao, PixPagador, PixRecebido, PixRecebidoListQuery,
+    PixRecebidoListResponse, PixTransactionDetailResponse, PixTransactionPayload,
+    PixTransactionResponse, SplitConfigResponse, SplitLinkResponse, SplitPayload, TipoCob,
+    WebhookPayload, WebhookResponse, WebhooksListResponse,
+};
+
+const FAKE_TIMESTAMP: &str = "2024-01-01T00:00:00.000Z";
+const FAKE_BILLING_TIMESTAMP: &str = "2024-01-01 00:00:00";
+const FAKE_EXPIRE_AT: &str = "2024-01-31";
+const DEFAULT_COB_EXPIRACAO: u32 = 86_400;
+
+/// In-memory implementation of every [`EfiApi`](crate::api::EfiApi) operation.
+///
+/// Records are kept in memory and can be inspected after the code under test
+/// ran. Missing records fail with the same typed `404` errors the real API returns.
+#[derive(Debug, Default)]
+pub struct FakeEfi {
+    state: Mutex<FakeState>,
+}
+
+#[derive(Debug, Default)]
+struct FakeState {
+    next_id: i64,
+    cobs: BTreeMap<String, CobResponse>,
+    cobvs: BTreeMap<String, CobvResponse>,
+    webhooks: BTreeMap<String, WebhookResponse>,
+    split_configs: BTreeMap<String, SplitConfigResponse>,
+    split_cob_links: HashMap<String, String>,
+    split_cobv_links: HashMap<String, String>,
+    pix_transactions: BTreeMap<String, PixTransactionDetailResponse>,
+    /// `idEnvio` to end-to-end id.
+    pix_envios: HashMap<String, String>,
+    pix_received: BTreeMap<String, PixRecebido>,
+    billing_charges: BTreeMap<i64, BillingChargeDetailData>,
+    billing_notifications: HashMap<String, BillingNotificationResponse>,
+    carnets: BTreeMap<i64, CarnetData>,
+}
+
+impl FakeEfi {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn cobs(&self) -> Vec<CobResponse> {
+        self.state().cobs.values().cloned().collect()
+    }
+
+    #[must_use]
+    pub fn cobvs(&self) -> Vec<CobvResponse> {
+        self.state().cobvs.values().cloned().collect()
+    }
+
+    #[must_use]
+    pub fn webhooks(&self) -> Vec<WebhookResponse> {
+        self.state().webhooks.values().cloned().collect()
+    }
+
+    #[must_use]
+    pub fn split_configs(&self) -> Vec<SplitConfigResponse> {
+        self.state().split_configs.values().cloned().collect()
+    }
+
+    #[must_use]
+    pub fn pix_transactions(&self) -> Vec<PixTransactionDetailResponse> {
+        self.state().pix_transactions.values().cloned().collect()
+    }
+
+    #[must_use]
+    pub fn billing_charges(&self) -> Vec<BillingChargeDetailData> {
+        self.state().billing_charges.values().cloned().collect()
+    }
+
+    /// Pix received through `pay_cob`, `pay_cobv` or `insert_pix_received`.
+    #[must_use]
+    pub fn pix_received(&self) -> Vec<PixRecebido> {
+        self.state().pix_received.values().cloned().collect()
+    }
+
+    #[must_use]
+    pub fn carnets(&self) -> Vec<CarnetData> {
+        self.state().carnets.values().cloned().collect()
+    }
+
+    /// Marks an immediate charge as paid, as if the payer settled it.
+    pub fn pay_cob(&self, txid: &str) -> Result<CobResponse, Error> {
+        Txid::parse(txid)?;
+        let mut state = self.state();
+        let id = state.next_id();
+        let cob = state
+            .cobs
+            .get_mut(txid)
+            .ok_or_else(|| not_found(Api::Pix))?;
+        if let Some(valor) = &cob.valor {
+            let pix = fake_pix_recebido(id, txid, valor, cob.chave.as_ref(), cob.devedor.as_ref());
+            cob.pix = Some(vec![pix]);
+        }
+        cob.status = Some(CobStatus::Concluida);
+        let cob = cob.clone();
+        for pix in cob.pix.iter().flatten() {
+            state
+                .pix_received
+                .insert(pix.end_to_end_id.clone(), pix.clone());
+        }
+        Ok(cob)
+    }
+
+    /// Marks a charge with due date as paid, as if the payer settled it.
+    pub fn pay_cobv(&self, txid: &str) -> Result<CobvResponse, Error> {
+        Txid::parse(txid)?;
+        let mut state = self.state();
+        let id = state.next_id();
+        let cobv = state
+            .cobvs
+            .get_mut(txid)
+            .ok_or_else(|| not_found(Api::Pix))?;
+        if let Some(valor) = &cobv.valor {
+            let pix = fake_pix_recebido(
+                id,
+                txid,
+                valor,
+                cobv.chave.as_ref(),
+                cobv.devedor.as_ref().map(|d| &d.pessoa),
+            );
+            cobv.pix = Some(vec![pix]);
+        }
+        cobv.status = Some(CobStatus::Concluida);
+        let cobv = cobv.clone();
+        for pix in cobv.pix.iter().flatten() {
+            state
+                .pix_received
+                .insert(pix.end_to_end_id.clone(), pix.clone());
+        }
+        Ok(cobv)
+    }
+
+    /// Marks a billing charge as paid, as if the payer settled it.
+    pub fn pay_billing_charge(&self, charge_id: i64) -> Result<BillingChargeDetailData, Error> {
+        let mut state = self.state();
+        let charge = state
+            .billing_charges
+            .get_mut(&charge_id)
+            .ok_or_else(|| not_found(Api::Billing))?;
+        charge.status = BillingChargeStatus::Paid;
+        charge.history.push(history_entry("Pagamento confirmado"));
+        Ok(charge.clone())
+    }
+
+    /// Registers a Pix received straight on a key, without a charge.
+    pub fn insert_pix_received(&self, pix: PixRecebido) {
+        self.state()
+            .pix_received
+            .insert(pix.end_to_end_id.clone(), pix);
+    }
+
+    /// Registers the response returned by `billing_notification_get(token)`.
+    pub fn insert_billing_notification(
+        &self,
+        token: impl Into<String>,
+        notification: BillingNotificationResponse,
+    ) {
+        self.state()
+            .billing_notifications
+            .insert(token.into(), notification);
+    }
+
+    fn state(&self) -> MutexGuard<'_, FakeState> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl FakeState {
+    fn next_id(&mut self) -> i64 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    fn next_txid(&mut self) -> String {
+        format!("fake{:028}", self.next_id())
+    }
+
+    /// Copies a received Pix over the one stored on the charge it paid, so
+    /// refunds show up in `cob_get` and `cobv_get` too.
+    fn sync_charge_pix(&mut self, pix: &PixRecebido) {
+        let Some(txid) = &pix.txid else {
+            return;
+        };
+        let charge_pix = self
+            .cobs
+            .get_mut(txid)
+            .and_then(|cob| cob.pix.as_mut())
+            .into_iter()
+            .chain(self.cobvs.get_mut(txid).and_then(|cobv| cobv.pix.as_mut()))
+            .flatten();
+        for stored in charge_pix {
+            if stored.end_to_end_id == pix.end_to_end_id {
+                stored.clone_from(pix);
+            }
+        }
+    }
+
+    fn billing_charge(&self, charge_id: i64) -> Result<&BillingChargeDetailData, Error> {
+        self.billing_charges
+            .get(&charge_id)
+            .ok_or_else(|| not_found(Api::Billing))
+    }
+
+    fn carnet_mut(&mut self, carnet_id: i64) -> Result<&mut CarnetData, Error> {
+        self.carnets
+            .get_mut(&carnet_id)
+            .ok_or_else(|| not_found(Api::Billing))
+    }
+
+    fn carnet_parcel_mut(
+        &mut self,
+        carnet_id: i64,
+        parcel: i32,
+    ) -> Result<&mut CarnetCharge, Error> {
+        let parcel = parcel.to_string();
+        self.carnet_mut(carnet_id)?
+            .charges
+            .iter_mut()
+            .find(|charge| charge.parcel == parcel)
+            .ok_or_else(|| not_found(Api::Billing))
+    }
+
+    fn insert_billing_charge(
+        &mut self,
+        items: &[BillingItem],
+        shippings: Option<&[BillingShipping]>,
+        customer: Option<BillingCustomer>,
+        status: BillingChargeStatus,
+    ) -> Result<BillingChargeData, Error> {
+        let shippings_total =
+            Brl::checked_sum(shippings.unwrap_or_default().iter().map(|s| s.value));
+        let total = items_total(items)?
+            .checked_add(shippings_total.ok_or_else(amount_overflow)?)
+            .ok_or_else(amount_overflow)?;
+        let charge_id = self.next_id();
+
+        let detail = BillingChargeDetailData {
+            charge_id,
+            total,
+            status: status.clone(),
+            custom_id: None,
+            created_at: FAKE_BILLING_TIMESTAMP.to_owned(),
+            notification_url: None,
+            items: items
+                .iter()
+                .map(|item| BillingItemResponse {
+                    name: item.name.clone(),
+                    value: item.value,
+                    amount: item.amount,
+                    marketplace: None,
+                })
+                .collect(),
+            history: vec![history_entry(&format!("Cobrança criada ({status})"))],
+            shippings: shippings.map(|shippings| {
+                shippings
+                    .iter()
+                    .map(|shipping| BillingChargeShipping {
+                        name: shipping.name.clone(),
+                        value: shipping.value,
+                        payee_code: None,
+                    })
+                    .collect()
+            }),
+            customer: customer.map(Into::into),
+            payment: fake_detail_payment(charge_id),
+        };
+
+        let data = charge_data(&detail);
+        self.billing_charges.insert(charge_id, detail);
+        Ok(data)
+    }
+}
+
+fn not_found(api: Api) -> Error {
+    let body = match api {
+        Api::Pix => json!({
+            "type": "https://pix.bcb.gov.br/api/v2/error/NaoEncontrado",
+            "title": "Não Encontrado",
+            "status": 404,
+            "detail": "Registro não encontrado no fake",
+        }),
+        Api::Billing => json!({
+            "code": 3_500_034,
+            "error": "resource_not_found",
+            "error_description": "Registro não encontrado no fake",
+        }),
+    };
+    Error::from_response(api, StatusCode::NOT_FOUND, body.to_string())
+}
+
+fn invalid_refund(detail: &str) -> Error {
+    let body = json!({
+        "type": "https://pix.bcb.gov.br/api/v2/error/DevolucaoOperacaoInvalida",
+        "title": "Operação Inválida",
+        "status": 400,
+        "detail": detail,
+    });
+    Error::from_response(Api::Pix, StatusCode::BAD_REQUEST, body.to_string())
+}
+
+fn txid_in_use() -> Error {
+    let body = json!({
+        "type": "https://pix.bcb.gov.br/api/v2/error/CobOperacaoInvalida",
+        "title": "Cobrança inválida.",
+        "status": 409,
+        "detail": "O txid informado já está em uso.",
+    });
+    Error::from_response(Api::Pix, StatusCode::CONFLICT, body.to_string())
+}
+
+fn invalid_period() -> Error {
+    let body = json!({
+        "code": 3_500_034,
+        "error": "validation_error",
+        "error_description": "Período inválido: use datas no formato AAAA-MM-DD",
+    });
+    Error::from_response(Api::Billing, StatusCode::BAD_REQUEST, body.to_string())
+}
+
+fn amount_overflow() -> Error {
+    let body = json!({
+        "code": 3_500_034,
+        "error": "validation_error",
+        "error_description": "Valor total fora do intervalo permitido",
+    });
+    Error::from_response(Api::Billing, StatusCode::BAD_REQUEST, body.to_string())
+}
+
+fn items_total(items: &[BillingItem]) -> Result<Brl, Error> {
+    let line_totals = items
+        .iter()
+        .map(|item| item.value.checked_mul(i64::from(item.amount)))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(amount_overflow)?;
+    Brl::checked_sum(line_totals).ok_or_else(amount_overflow)
+}
+
+fn fake_pix(id: i64) -> BillingPix {
+    BillingPix {
+        qrcode: format!("00020101021226830014BR.GOV.BCB.PIX2561fake/{id}5204000053039865802BR"),
+        qrcode_image: format!("data:image/svg+xml;base64,fake-{id}"),
+    }
+}
+
+fn fake_detail_payment(charge_id: i64) -> BillingChargeDetailPayment {
+    BillingChargeDetailPayment {
+        method: "banking_billet".to_owned(),
+        created_at: FAKE_BILLING_TIMESTAMP.to_owned(),
+        message: None,
+        banking_billet: BillingChargeDetailBankingBillet {
+            barcode: format!("00000.00000 00000.000000 00000.000000 0 {charge_id:014}"),
+            pix: fake_pix(charge_id),
+            link: format!("https://fake.efi/charge/{charge_id}"),
+            billet_link: format!("https://fake.efi/billet/{charge_id}"),
+            pdf: BillingPdf {
+                charge: format!("https://fake.efi/charge/{charge_id}.pdf"),
+            },
+            expire_at: fake_expire_at(),
+        },
+        credit_card: None,
+    }
+}
+
+fn fake_timestamp() -> Timestamp {
+    FAKE_TIMESTAMP.parse().expect("FAKE_TIMESTAMP is valid")
+}
+
+fn fake_expire_at() -> Date {
+    FAKE_EXPIRE_AT.parse().expect("FAKE_EXPIRE_AT is valid")
+}
+
+fn history_entry(message: &str) -> BillingChargeHistoryEntry {
+    BillingChargeHistoryEntry {
+        message: message.to_owned(),
+        created_at: FAKE_BILLING_TIMESTAMP.to_owned(),
+    }
+}
+
+fn charge_data(detail: &BillingChargeDetailData) -> BillingChargeData {
+    let billet = &detail.payment.banking_billet;
+    BillingChargeData {
+        barcode: billet.barcode.clone(),
+        pix: Some(billet.pix.clone()),
+        link: billet.link.clone(),
+        billet_link: billet.billet_link.clone(),
+        pdf: billet.pdf.clone(),
+        expire_at: billet.expire_at,
+        charge_id: detail.charge_id,
+        status: detail.status.clone(),
+        total: detail.total,
+        payment: detail.payment.method.clone(),
+    }
+}
+
+fn fake_loc(id: i64, tipo_cob: TipoCob) -> CobLoc {
+    CobLoc {
+        id,
+        location: format!("fake.efipay.com.br/v2/loc/{id}"),
+        tipo_cob,
+        criacao: Some(fake_timestamp()),
+    }
+}
+
+fn fake_pix_recebido(
+    id: i64,
+    txid: &str,
+    valor: &CobValor,
+    chave: Option<&String>,
+    devedor: Option<&DevedorResponse>,
+) -> PixRecebido {
+    PixRecebido {
+        end_to_end_id: format!("E09089356202401010000{id:011}"),
+        txid: Some(txid.to_owned()),
+        valor: valor.original,
+        chave: chave.cloned(),
+        horario: fake_timestamp(),
+        info_pagador: None,
+        componentes_valor: Some(ComponentesValor {
+            original: Some(ComponenteValor {
+                valor: valor.original,
+                modalidade_agente: None,
+                prestador_de_servico_de_saque: None,
+            }),
+            saque: None,
+            troco: None,
+            juros: None,
+            multa: None,
+            abatimento: None,
+            desconto: None,
+        }),
+        devolucoes: Vec::new(),
+        gn_extras: devedor.map(|devedor| PixGnExtras {
+            pagador: Some(PixPagador {
+                nome: devedor.nome.clone(),
+                cpf: devedor.cpf.clone(),
+                cnpj: devedor.cnpj.clone(),
+                codigo_banco: None,
+            }),
+        }),
+    }
+}
+
+fn enviado_favorecido(favorecido: &PixFavorecido) -> PixEnviadoFavorecido {
+    match favorecido {
+        PixFavorecido::Chave { chave, cpf, cnpj } => PixEnviadoFavorecido {
+            chave: Some(chave.to_string()),
+            identificacao: (cpf.is_some() || cnpj.is_some()).then(|| PixIdentificacao {
+                nome: None,
+                cpf: cpf.as_ref().map(ToString::to_string),
+                cnpj: cnpj.as_ref().map(ToString::to_string),
+            }),
+            conta_banco: None,
+        },
+        PixFavorecido::ContaBanco { conta_banco } => PixEnviadoFavorecido {
+            chave: None,
+            identificacao: None,
+            conta_banco: Some(PixEnviadoContaBanco {
+                titular: PixIdentificacao {
+                    nome: Some(conta_banco.titular.nome().to_owned()),
+                    cpf: conta_banco.titular.cpf().map(ToString::to_string),
+                    cnpj: conta_banco.titular.cnpj().map(ToString::to_string),
+                },
+                codigo_banco: Some(conta_banco.codigo_banco.clone()),
+                agencia: Some(conta_banco.agencia.clone()),
+                conta: Some(conta_banco.conta.clone()),
+                tipo_conta: Some(conta_banco.tipo_conta),
+            }),
+        },
+    }
+}
+
+fn cob_response(txid: String, loc: CobLoc, payload: &CobPayload) -> CobResponse {
+    CobResponse {
+        txid,
+        revisao: Some(0),
+        location: Some(loc.location.clone()),
+        loc: Some(loc),
+        calendario: Some(CobCalendarioResponse {
+            criacao: Some(fake_timestamp()),
+            expiracao: Some(
+                payload
+                    .calendario
+                    .expiracao
+                    .unwrap_or(DEFAULT_COB_EXPIRACAO),
+            ),
+        }),
+        devedor: payload.devedor.clone().map(Into::into),
+        valor: Some(payload.valor.clone()),
+        chave: Some(payload.chave.to_string()),
+        solicitacao_pagador: payload.solicitacao_pagador.clone(),
+        info_adicionais: payload.info_adicionais.clone(),
+        pix_copia_e_cola: None,
+        status: Some(CobStatus::Ativa),
+        pix: None,
+    }
+}
+
+fn cobv_response(txid: String, loc: CobLoc, payload: &CobvPayload) -> CobvResponse {
+    CobvResponse {
+        txid,
+        revisao: Some(0),
+        location: Some(loc.location.clone()),
+        loc: Some(loc),
+        calendario: Some(CobvCalendarioResponse {
+            criacao: Some(fake_timestamp()),
+            data_de_vencimento: Some(payload.calendario.data_de_vencimento),
+            validade_apos_vencimento: Some(payload.calendario.validade_apos_vencimento),
+        }),
+        devedor: payload.devedor.clone().map(Into::into),
+        valor: Some(payload.valor.clone()),
+        chave: Some(payload.chave.to_string()),
+        solicitacao_pagador: payload.solicitacao_pagador.clone(),
+        info_adicionais: payload.info_adicionais.clone(),
+        pix_copia_e_cola: None,
+        status: Some(CobStatus::Ativa),
+        pix: None,
+    }
+}
+
+/// Rewrites `existing` from `payload`, keeping what the server owns: the
+/// location, status, received Pix and creation time. Bumps `revisao`.
+fn revise_cob(existing: &mut CobResponse, payload: &CobPayload) {
+    let loc = existing
+        .loc
+        .take()
+        .unwrap_or_else(|| fake_loc(0, TipoCob::Cob));
+    let mut updated = cob_response(existing.txid.clone(), loc, payload);
+    updated.status = existing.status.take();
+    updated.revisao = existing.revisao.map(|revisao| revisao + 1);
+    updated.pix = existing.pix.take();
+    if let (Some(calendario), Some(previous)) =
+        (updated.calendario.as_mut(), existing.calendario.as_mut())
+    {
+        calendario.criacao = previous.criacao.take();
+    }
+    *existing = updated;
+}
+
+fn revise_cobv(existing: &mut CobvResponse, payload: &CobvPayload) {
+    let loc = existing
+        .loc
+        .take()
+        .unwrap_or_else(|| fake_loc(0, TipoCob::Cobv));
+    let mut updated = cobv_response(existing.txid.clone(), loc, payload);
+    updated.status = existing.status.take();
+    updated.revisao = existing.revisao.map(|revisao| revisao + 1);
+    updated.pix = existing.pix.take();
+    if let (Some(calendario), Some(previous)) =
+        (updated.calendario.as_mut(), existing.calendario.as_mut())
+    {
+        calendario.criacao = previous.criacao.take();
+    }
+    *existing = updated;
+}
+
+/// Whether a charge passes the `cob_list` filters. `inicio` and `fim` are
+/// compared by calendar date only, since every fake charge shares one
+/// creation time.
+fn matches_query(
+    query: &CobListQuery,
+    criacao: Option<&Timestamp>,
+    devedor: Option<&DevedorResponse>,
+    status: Option<&CobStatus>,
+    has_location: bool,
+) -> bool {
+    criacao
+        .is_some_and(|criacao| (query.inicio.date()..=query.fim.date()).contains(&criacao.date()))
+        && query
+            .cpf
+            .as_ref()
+            .is_none_or(|cpf| devedor.and_then(|d| d.cpf.as_deref()) == Some(cpf.as_str()))
+        && query
+            .cnpj
+            .as_ref()
+            .is_none_or(|cnpj| devedor.and_then(|d| d.cnpj.as_deref()) == Some(cnpj.as_str()))
+        && query
+            .status
+            .as_ref()
+            .is_none_or(|wanted| status == Some(wanted))
+        && query
+            .location_presente
+            .is_none_or(|wanted| wanted == has_location)
+}
+
+/// Cuts the requested page out of `items`, 100 per page unless the query
+/// says otherwise.
+fn page_of<T>(
+    inicio: &Timestamp,
+    fim: &Timestamp,
+    pagina_atual: Option<u32>,
+    itens_por_pagina: Option<u32>,
+    items: Vec<T>,
+) -> (Vec<T>, ListParametros) {
+    let pagina_atual = pagina_atual.unwrap_or(0);
+    let itens_por_pagina = itens_por_pagina.unwrap_or(100).max(1);
+    let total = u32::try_from(items.len()).unwrap_or(u32::MAX);
+    let page = items
+        .into_iter()
+        .skip(pagina_atual as usize * itens_por_pagina as usize)
+        .take(itens_por_pagina as usize)
+        .collect();
+    let parametros = ListParametros {
+        inicio: inicio.clone(),
+        fim: fim.clone(),
+        paginacao: Paginacao {
+            pagina_atual,
+            itens_por_pagina,
+            quantidade_de_paginas: total.div_ceil(itens_por_pagina),
+            quantidade_total_de_itens: total,
+        },
+    };
+    (page, parametros)
+}
+
+#[async_trait]
+impl PixChargesApi for FakeEfi {
+    async fn cob_create(&self, payload: &CobPayload) -> Result<CobResponse, Error> {
+        let mut state = self.state();
+        let txid = state.next_txid();
+        let loc = fake_loc(state.next_id(), TipoCob::Cob);
+        let cob = cob_response(txid.clone(), loc, payload);
+        state.cobs.insert(txid, cob.clone());
+        Ok(cob)
+    }
+
+    async fn cob_create_with_txid(
+        &self,
+        txid: &Txid,
+        payload: &CobPayload,
+    ) -> Result<CobResponse, Error> {
+        let mut state = self.state();
+        if state.cobs.contains_key(txid.as_str()) {
+            return Err(txid_in_use());
+        }
+        let loc = fake_loc(state.next_id(), TipoCob::Cob);
+        let cob = cob_response(txid.to_string(), loc, payload);
+        state.cobs.insert(txid.to_string(), cob.clone());
+        Ok(cob)
+    }
+
+    /// The same `PUT` as `cob_create_with_txid`, so an existing txid is
+    /// rejected with `409`; charges are revised with `cob_patch`.
+    async fn cob_update(&self, txid: &str, payload: &CobPayload) -> Result<CobResponse, Error> {
+        self.cob_create_with_txid(&Txid::parse(txid)?, payload)
+            .await
+    }
+
+    async fn cob_patch(&self, txid: &str, payload: &CobPayload) -> Result<CobResponse, Error> {
+        Txid::parse(txid)?;
+        let mut state = self.state();
+        let existing = state
+            .cobs
+            .get_mut(txid)
+            .ok_or_else(|| not_found(Api::Pix))?;
+        revise_cob(existing, payload);
+        Ok(existing.clone())
+    }
+
+    async fn cob_get(&self, txid: &str) -> Result<CobResponse, Error> {
+        Txid::parse(txid)?;
+        self.state()
+            .cobs
+            .get(txid)
+            .cloned()
+            .ok_or_else(|| not_found(Api::Pix))
+    }
+
+    async fn cob_list(&self, query: &CobListQuery) -> Result<CobListResponse, Error> {
+        let state = self.state();
+        let cobs = state
+            .cobs
+            .values()
+            .filter(|cob| {
+                matches_query(
+                    query,
+                    cob.calendario.as_ref().and_then(|c| c.criacao.as_ref()),
+                    cob.devedor.as_ref(),
+                    cob.status.as_ref(),
+                    cob.loc.is_some(),
+                )
+            })
+            .cloned()
+            .collect();
+        let (cobs, parametros) = page_of(
+            &query.inicio,
+            &query.fim,
+            query.pagina_atual,
+            query.itens_por_pagina,
+            cobs,
+        );
+        Ok(CobListResponse { parametros, cobs })
+    }
+
+    async fn cobv_create(&self, payload: &CobvPayload) -> Result<CobvResponse, Error> {
+        let mut state = self.state();
+        let txid = state.next_txid();
+        let loc = fake_loc(state.next_id(), TipoCob::Cobv);
+        let cobv = cobv_response(txid.clone(), loc, payload);
+        state.cobvs.insert(txid, cobv.clone());
+        Ok(cobv)
+    }
+
+    async fn cobv_update(&self, txid: &str, payload: &CobvPayload) -> Result<CobvResponse, Error> {
+        Txid::parse(txid)?;
+        let mut state = self.state();
+        if state.cobvs.contains_key(txid) {
+            return Err(txid_in_use());
+        }
+        let loc = fake_loc(state.next_id(), TipoCob::Cobv);
+        let cobv = cobv_response(txid.to_owned(), loc, payload);
+        state.cobvs.insert(txid.to_owned(), cobv.clone());
+        Ok(cobv)
+    }
+
+    async fn cobv_patch(&self, txid: &str, payload: &CobvPayload) -> Result<CobvResponse, Error> {
+        Txid::parse(txid)?;
+        let mut state = self.state();
+        let existing = state
+            .cobvs
+            .get_mut(txid)
+            .ok_or_else(|| not_found(Api::Pix))?;
+        revise_cobv(existing, payload);
+        Ok(existing.clone())
+    }
+
+    async fn cobv_get(&self, txid: &str) -> Result<CobvResponse, Error> {
+        Txid::parse(txid)?;
+        self.state()
+            .cobvs
+            .get(txid)
+            .cloned()
+            .ok_or_else(|| not_found(Api::Pix))
+    }
+
+    async fn cobv_list(&self, query: &CobListQuery) -> Result<CobvListResponse, Error> {
+        let state = self.state();
+        let cobvs = state
+            .cobvs
+            .values()
+            .filter(|cobv| {
+                matches_query(
+                    query,
+                    cobv.calendario.as_ref().and_then(|c| c.criacao.as_ref()),
+                    cobv.devedor.as_ref().map(|d| &d.pessoa),
+                    cobv.status.as_ref(),
+                    cobv.loc.is_some(),
+                )
+            })
+            .cloned()
+            .collect();
+        let (cobs, parametros) = page_of(
+            &query.inicio,
+            &query.fim,
+            query.pagina_atual,
+            query.itens_por_pagina,
+            cobvs,
+        );
+        Ok(CobvListResponse { parametros, cobs })
+    }
+}
+
+#[async_trait]
+impl PixTransfersApi for FakeEfi {
+    /// Settles transfers immediately. Sending again with the same `idEnvio`
+    /// returns the transfer already recorded under it.
+    async fn pix_send(
+        &self,
+        payload: &PixTransactionPayload,
+    ) -> Result<PixTransactionResponse, Error> {
+        let mut state = self.state();
+        let id_envio = payload.id_envio();
+
+        let existing = state
+            .pix_envios
+            .get(&id_envio)
+            .and_then(|end_to_end_id| state.pix_transactions.get(end_to_end_id));
+
+        let transaction = if let Some(existing) = existing {
+            existing.clone()
+        } else {
+            let id = state.next_id();
+            let transaction = PixTransactionDetailResponse {
+                end_to_end_id: format!("E00000000202401010000{id:011}"),
+                id_envio: Some(id_envio.clone()),
+                valor: payload.valor,
+                horario: PixEnvioHorario {
+                    solicitacao: Some(fake_timestamp()),
+                    liquidacao: Some(fake_timestamp()),
+                },
+                status: PixTransferStatus::Realizado,
+                pagador: Some(PixEnviadoPagador {
+                    chave: Some(payload.pagador.chave.to_string()),
+                    info_pagador: payload.pagador.info_pagador.clone(),
+                }),
+                favorecido: Some(enviado_favorecido(&payload.favorecido)),
+            };
+            state
+                .pix_envios
+                .insert(id_envio.clone(), transaction.end_to_end_id.clone());
+            state
+                .pix_transactions
+                .insert(transaction.end_to_end_id.clone(), transaction.clone());
+            transaction
+        };
+
+        Ok(PixTransactionResponse {
+            id_envio,
+            end_to_end_id: transaction.end_to_end_id,
+            valor: transaction.valor,
+            horario: transaction.horario,
+            status: transaction.status,
+        })
+    }
+
+    async fn pix_get_transaction(
+        &self,
+        end_to_end_id: &str,
+    ) -> Result<PixTransactionDetailResponse, Error> {
+        check_id("end-to-end id", end_to_end_id)?;
+        self.state()
+            .pix_transactions
+            .get(end_to_end_id)
+            .cloned()
+            .ok_or_else(|| not_found(Api::Pix))
+    }
+
+    async fn pix_get_transaction_by_id_envio(
+        &self,
+        id_envio: &str,
+    ) -> Result<PixTransactionDetailResponse, Error> {
+        check_id("idEnvio", id_envio)?;
+        let state = self.state();
+        state
+            .pix_envios
+            .get(id_envio)
+            .and_then(|end_to_end_id| state.pix_transactions.get(end_to_end_id))
+            .cloned()
+            .ok_or_else(|| not_found(Api::Pix))
+    }
+
+    /// Sent Pix are never refunded in the fake, so `devolucao_presente(true)`
+    /// matches nothing.
+    async fn pix_sent_list(
+        &self,
+        query: &PixEnviadoListQuery,
+    ) -> Result<PixEnviadoListResponse, Error> {
+        let state = self.state();
+        let pix = state
+            .pix_transactions
+            .values()
+            .filter(|pix| {
+                pix.horario.solicitacao.as_ref().is_some_and(|solicitacao| {
+                    (query.inicio.date()..=query.fim.date()).contains(&solicitacao.date())
+                }) && query
+                    .status
+                    .as_ref()
+                    .is_none_or(|status| *status == pix.status)
+                    && query.devolucao_presente != Some(true)
+            })
+            .cloned()
+            .collect();
+        let (pix, parametros) = page_of(
+            &query.inicio,
+            &query.fim,
+            query.pagina_atual,
+            query.itens_por_pagina,
+            pix,
+        );
+        Ok(PixEnviadoListResponse { parametros, pix })
+    }
+
+    async fn pix_received_list(
+        &self,
+        query: &PixRecebidoListQuery,
+    ) -> Result<PixRecebidoListResponse, Error> {
+        let state = self.state();
+        let pix = state
+            .pix_received
+            .values()
+            .filter(|pix| {
+                let pagador = pix.gn_extras.as_ref().and_then(|e| e.pagador.as_ref());
+                (query.inicio.date()..=query.fim.date()).contains(&pix.horario.date())
+                    && query
+                        .txid
+                        .as_ref()
+                        .is_none_or(|txid| pix.txid.as_ref() == Some(txid))
+                    && query
+                        .txid_presente
+                        .is_none_or(|wanted| wanted == pix.txid.is_some())
+                    && query
+                        .devolucao_presente
+                        .is_none_or(|wanted| wanted != pix.devolucoes.is_empty())
+                    && query.cpf.as_ref().is_none_or(|cpf| {
+                        pagador.and_then(|p| p.cpf.as_deref()) == Some(cpf.as_str())
+                    })
+                    && query.cnpj.as_ref().is_none_or(|cnpj| {
+                        pagador.and_then(|p| p.cnpj.as_deref()) == Some(cnpj.as_str())
+                    })
+            })
+            .cloned()
+            .collect();
+        let (pix, parametros) = page_of(
+            &query.inicio,
+            &query.fim,
+            query.pagina_atual,
+            query.itens_por_pagina,
+            pix,
+        );
+        Ok(PixRecebidoListResponse { parametros, pix })
+    }
+
+    async fn pix_received_get(&self, end_to_end_id: &str) -> Result<PixRecebido, Error> {
+        check_id("end-to-end id", end_to_end_id)?;
+        self.state()
+            .pix_received
+            .get(end_to_end_id)
+            .cloned()
+            .ok_or_else(|| not_found(Api::Pix))
+    }
+
+    /// Settles refunds immediately. Reusing a refund id returns the refund
+    /// already recorded under it.
+    async fn pix_refund_create(
+        &self,
+        end_to_end_id: &str,
+        refund_id: &str,
+        payload: &DevolucaoPayload,
+    ) -> Result<Devolucao, Error> {
+        check_id("end-to-end id", end_to_end_id)?;
+        check_id("refund id", refund_id)?;
+        let mut state = self.state();
+        let id = state.next_id();
+        let pix = state
+            .pix_received
+            .get_mut(end_to_end_id)
+            .ok_or_else(|| not_found(Api::Pix))?;
+        if let Some(existing) = pix.devolucoes.iter().find(|d| d.id == refund_id) {
+            return Ok(existing.clone());
+        }
+
+        if payload.valor.is_zero() || payload.valor.is_negative() {
+            return Err(invalid_refund("Valor da devolução deve ser positivo"));
+        }
+        let refunded = Brl::checked_sum(
+            pix.devolucoes
+                .iter()
+                .filter(|d| d.status != DevolucaoStatus::NaoRealizado)
+                .map(|d| d.valor),
+        )
+        .and_then(|refunded| refunded.checked_add(payload.valor))
+        .ok_or_else(|| invalid_refund("Valor da devolução fora do intervalo permitido"))?;
+        if refunded > pix.valor {
+            return Err(invalid_refund(
+                "Valor da devolução excede o valor disponível do Pix",
+            ));
+        }
+
+        let devolucao = Devolucao {
+            id: refund_id.to_owned(),
+            rtr_id: Some(format!("D09089356202401010000{id:011}")),
+            valor: payload.valor,
+            natureza: Some(payload.natureza.unwrap_or(NaturezaDevolucao::Original)),
+            descricao: payload.descricao.clone(),
+            horario: DevolucaoHorario {
+                solicitacao: Some(fake_timestamp()),
+                liquidacao: Some(fake_timestamp()),
+            },
+            status: DevolucaoStatus::Devolvido,
+            motivo: None,
+        };
+        pix.devolucoes.push(devolucao.clone());
+        let pix = pix.clone();
+        state.sync_charge_pix(&pix);
+        Ok(devolucao)
+    }
+
+    async fn pix_refund_get(
+        &self,
+        end_to_end_id: &str,
+        refund_id: &str,
+    ) -> Result<Devolucao, Error> {
+        check_id("end-to-end id", end_to_end_id)?;
+        check_id("refund id", refund_id)?;
+        self.state()
+            .pix_received
+            .get(end_to_end_id)
+            .and_then(|pix| pix.devolucoes.iter().find(|d| d.id == refund_id))
+            .cloned()
+            .ok_or_else(|| not_found(Api::Pix))
+    }
+}
+
+#[async_trait]
+impl WebhooksApi for FakeEfi {
+    async fn webhook_create(&self, payload: &WebhookPayload) -> Result<WebhookResponse, Error> {
+        let mut state = self.state();
+        let id = state.next_id().to_string();
+        let webhook = WebhookResponse {
+            id: id.clone(),
+            url: payload.url.clone(),
+            chave: Some(payload.chave.to_string()),
+            criacao_date_time: Some(fake_timestamp()),
+        };
+        state.webhooks.insert(id, webhook.clone());
+        Ok(webhook)
+    }
+
+    async fn webhook_update(
+        &self,
+        webhook_id: &str,
+        payload: &WebhookPayload,
+    ) -> Result<WebhookResponse, Error> {
+        let mut state = self.state();
+        let webhook = state
+            .webhooks
+            .get_mut(webhook_id)
+            .ok_or_else(|| not_found(Api::Pix))?;
+        webhook.url.clone_from(&payload.url);
+        webhook.chave = Some(payload.chave.to_string());
+        Ok(webhook.clone())
+    }
+
+    async fn webhook_get(&self, webhook_id: &str) -> Result<WebhookResponse, Error> {
+        self.state()
+            .webhooks
+            .get(webhook_id)
+            .cloned()
+            .ok_or_else(|| not_found(Api::Pix))
+    }
+
+    async fn webhook_list(&self) -> Result<WebhooksListResponse, Error> {
+        Ok(WebhooksListResponse {
+            webhooks: self.webhooks(),
+        })
+    }
+
+    async fn webhook_delete(&self, webhook_id: &str) -> Result<(), Error> {
+        self.state()
+            .webhooks
+            .remove(webhook_id)
+            .map(|_| ())
+            .ok_or_else(|| not_found(Api::Pix))
+    }
+}
+
+#[async_trait]
+impl SplitApi for FakeEfi {
+    async fn split_create_config(
+        &self,
+        payload: &SplitPayload,
+    ) -> Result<SplitConfigResponse, Error> {
+        let mut state = self.state();
+        let id = format!("fake-split-{}", state.next_id());
+        let config = SplitConfigResponse {
+            id: id.clone(),
+            descricao: Some(payload.descricao.clone()),
+            lancamento: Some(payload.lancamento.clone()),
+            split: Some(payload.split.clone()),
+        };
+        state.split_configs.insert(id, config.clone());
+        Ok(config)
+    }
+
+    async fn split_update_config(
+        &self,
+        config_id: &str,
+        payload: &SplitPayload,
+    ) -> Result<SplitConfigResponse, Error> {
+        let config = SplitConfigResponse {
+            id: config_id.to_owned(),
+            descricao: Some(payload.descricao.clone()),
+            lancamento: Some(payload.lancamento.clone()),
+            split: Some(payload.split.clone()),
+        };
+        self.state()
+            .split_configs
+            .insert(config_id.to_owned(), config.clone());
+        Ok(config)
+    }
+
+    async fn split_get_config(&self, config_id: &str) -> Result<SplitConfigResponse, Error> {
+        self.state()
+            .split_configs
+            .get(config_id)
+            .cloned()
+            .ok_or_else(|| not_found(Api::Pix))
+    }
+
+    async fn split_link_cob(
+        &self,
+        txid: &str,
+        split_config_id: &str,
+    ) -> Result<SplitLinkResponse, Error> {
+        Txid::parse(txid)?;
+        let mut state = self.state();
+        if !state.cobs.contains_key(txid) || !state.split_configs.contains_key(split_config_id) {
+            return Err(not_found(Api::Pix));
+        }
+        state
+            .split_cob_links
+            .insert(txid.to_owned(), split_config_id.to_owned());
+        Ok(linked())
+    }
+
+    async fn split_get_cob(&self, txid: &str) -> Result<SplitConfigResponse, Error> {
+        Txid::parse(txid)?;
+        let state = self.state();
+        state
+            .split_cob_links
+            .get(txid)
+            .and_then(|config_id| state.split_configs.get(config_id))
+            .cloned()
+            .ok_or_else(|| not_found(Api::Pix))
+    }
+
+    async fn split_unlink_cob(&self, txid: &str) -> Result<SplitLinkResponse, Error> {
+        Txid::parse(txid)?;
+        self.state()
+            .split_cob_links
+            .remove(txid)
+            .map(|_| unlinked())
+            .ok_or_else(|| not_found(Api::Pix))
+    }
+
+    async fn split_link_cobv(
+        &self,
+        txid: &str,
+        split_config_id: &str,
+    ) -> Result<SplitLinkResponse, Error> {
+        Txid::parse(txid)?;
+        let mut state = self.state();
+        if !state.cobvs.contains_key(txid) || !state.split_configs.contains_key(split_config_id) {
+            return Err(not_found(Api::Pix));
+        }
+        state
+            .split_cobv_links
+            .insert(txid.to_owned(), split_config_id.to_owned());
+        Ok(linked())
+    }
+
+    async fn split_get_cobv(&self, txid: &str) -> Result<SplitConfigResponse, Error> {
+        Txid::parse(txid)?;
+        let state = self.state();
+        state
+            .split_cobv_links
+            .get(txid)
+            .and_then(|config_id| state.split_configs.get(config_id))
+            .cloned()
+            .ok_or_else(|| not_found(Api::Pix))
+    }
+
+    async fn split_unlink_cobv(&self, txid: &str) -> Result<SplitLinkResponse, Error> {
+        Txid::parse(txid)?;
+        self.state()
+            .split_cobv_links
+            .remove(txid)
+            .map(|_| unlinked())
+            .ok_or_else(|| not_found(Api::Pix))
+    }
+}
+
+fn linked() -> SplitLinkResponse {
+    SplitLinkResponse {
+        status: "VINCULADO".to_owned(),
+        message: None,
+    }
+}
+
+fn unlinked() -> SplitLinkResponse {
+    SplitLinkResponse {
+        status: "DESVINCULADO".to_owned(),
+        message: None,
+    }
+}
+
+#[async_trait]
+impl BillingApi for FakeEfi {
+    async fn billing_charge_create(
+        &self,
+        payload: &BillingChargeCreateRequest,
+    ) -> Result<BillingChargeResponse, Error> {
+        let data = self.state().insert_billing_charge(
+            &payload.items,
+            None,
+            None,
+            BillingChargeStatus::New,
+        )?;
+        Ok(BillingChargeResponse { code: 200, data })
+    }
+
+    async fn billing_charge_one_step(
+        &self,
+        payload: &BillingChargeOneStepRequest,
+    ) -> Result<BillingChargeResponse, Error> {
+        let customer = payload
+            .payment
+            .banking_billet
+            .as_ref()
+            .map(|billet| billet.customer.clone());
+        let data = self.state().insert_billing_charge(
+            &payload.items,
+            payload.shippings.as_deref(),
+            customer,
+            BillingChargeStatus::Waiting,
+        )?;
+        Ok(BillingChargeResponse { code: 200, data })
+    }
+
+    async fn billing_charge_pay(
+        &self,
+        charge_id: i64,
+        payload: &BillingChargePayRequest,
+    ) -> Result<BillingChargeResponse, Error> {
+        let mut state = self.state();
+        let charge = state
+            .billing_charges
+            .get_mut(&charge_id)
+            .ok_or_else(|| not_found(Api::Billing))?;
+        charge.status = BillingChargeStatus::Waiting;
+        charge
+            .history
+            .push(history_entry("Forma de pagamento selecionada"));
+        if let Some(billet) = &payload.payment.banking_billet {
+            charge.customer = Some(billet.customer.clone().into());
+        }
+        Ok(BillingChargeResponse {
+            code: 200,
+            data: charge_data(charge),
+        })
+    }
+
+    async fn billing_charge_get(
+        &self,
+        charge_id: i64,
+    ) -> Result<BillingChargeDetailResponse, Error> {
+        Ok(BillingChargeDetailResponse {
+            code: 200,
+            data: self.state().billing_charge(charge_id)?.clone(),
+        })
+    }
+
+    async fn billing_notification_get(
+        &self,
+        token: &str,
+    ) -> Result<BillingNotificationResponse, Error> {
+        self.state()
+            .billing_notifications
+            .get(token)
+            .cloned()
+            .ok_or_else(|| not_found(Api::Billing))
+    }
+}
+
+fn carnet_response(carnet: &CarnetData) -> CarnetResponse {
+    CarnetResponse {
+        code: 200,
+        data: carnet.clone(),
+    }
+}
+
+#[async_trait]
+impl CarnetApi for FakeEfi {
+    async fn carnet_create(&self, payload: &CarnetCreateRequest) -> Result<CarnetResponse, Error> {
+        let mut state = self.state();
+        let carnet_id = state.next_id();
+        let value = items_total(&payload.items)?;
+
+        let charges = (1..=payload.repeats)
+            .zip(0..)
+            .map(|(parcel, months)| {
+                let expire_at = payload.expire_at.add_months(months)?;
+                let charge_id = state.next_id();
+                Ok(CarnetCharge {
+                    charge_id,
+                    parcel: parcel.to_string(),
+                    status: BillingChargeStatus::Waiting,
+                    value,
+                    expire_at,
+                    url: format!("https://fake.efi/charge/{charge_id}"),
+                    parcel_link: format!("https://fake.efi/parcel/{charge_id}"),
+                    pdf: CarnetChargePdf {
+                        charge: format!("https://fake.efi/charge/{charge_id}.pdf"),
+                    },
+                    barcode: format!("00000.00000 00000.000000 00000.000000 0 {charge_id:014}"),
+                    pix: fake_pix(charge_id),
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+        let metadata = payload.metadata.as_ref();
+
+        let carnet = CarnetData {
+            carnet_id,
+            status: CarnetStatus::Active,
+            cover: format!("https://fake.efi/carnet/{carnet_id}/cover"),
+            link: format!("https://fake.efi/carnet/{carnet_id}"),
+            carnet_link: format!("https://fake.efi/carnet/{carnet_id}/link"),
+            pdf: CarnetPdf {
+                carnet: format!("https://fake.efi/carnet/{carnet_id}.pdf"),
+                cover: format!("https://fake.efi/carnet/{carnet_id}/cover.pdf"),
+            },
+            charges,
+            custom_id: metadata.and_then(|metadata| metadata.custom_id.clone()),
+            notification_url: metadata.and_then(|metadata| metadata.notification_url.clone()),
+            created_at: Some(FAKE_BILLING_TIMESTAMP.to_owned()),
+            history: vec![history_entry("Carnê criado")],
+        };
+        state.carnets.insert(carnet_id, carnet.clone());
+        Ok(carnet_response(&carnet))
+    }
+
+    async fn carnet_get(&self, carnet_id: i64) -> Result<CarnetResponse, Error> {
+        Ok(carnet_response(self.state().carnet_mut(carnet_id)?))
+    }
+
+    async fn carnet_list(&self, begin_date: &str, end_date: &str) -> Result<CarnetResponse, Error> {
+        let begin: Date = begin_date.parse().map_err(|_| invalid_period())?;
+        let end: Date = end_date.parse().map_err(|_| invalid_period())?;
+        self.state()
+            .carnets
+            .values()
+            .rev()
+            .find(|carnet| {
+                carnet
+                    .created_at
+                    .as_deref()
+                    .and_then(|created_at| created_at.get(..10)?.parse::<Date>().ok())
+                    .is_some_and(|created| (begin..=end).contains(&created))
+            })
+            .map(carnet_response)
+            .ok_or_else(|| not_found(Api::Billing))
+    }
+
+    async fn carnet_update_metadata(
+        &self,
+        carnet_id: i64,
+        payload: &CarnetMetadataRequest,
+    ) -> Result<CarnetResponse, Error> {
+        let mut state = self.state();
+        let carnet = state.carnet_mut(carnet_id)?;
+        if let Some(custom_id) = &payload.custom_id {
+            carnet.custom_id = Some(custom_id.clone());
+        }
+        if let Some(notification_url) = &payload.notification_url {
+            carnet.notification_url = Some(notification_url.clone());
+        }
+        Ok(carnet_response(carnet))
+    }
+
+    async fn carnet_update_parcel(
+        &self,
+        carnet_id: i64,
+        parcel: i32,
+        payload: &CarnetParcelRequest,
+    ) -> Result<CarnetResponse, Error> {
+        let mut state = self.state();
+        state.carnet_parcel_mut(carnet_id, parcel)?.expire_at = payload.expire_at;
+        Ok(carnet_response(state.carnet_mut(carnet_id)?))
+    }
+
+    async fn carnet_update_parcels(
+        &self,
+        carnet_id: i64,
+        payload: &CarnetParcelsRequest,
+    ) -> Result<CarnetResponse, Error> {
+        let mut state = self.state();
+        for parcel in &payload.parcels {
+            state.carnet_parcel_mut(carnet_id, parcel.parcel)?.expire_at = parcel.expire_at;
+        }
+        Ok(carnet_response(state.carnet_mut(carnet_id)?))
+    }
+
+    async fn carnet_cancel(&self, carnet_id: i64) -> Result<CarnetResponse, Error> {
+        let mut state = self.state();
+        let carnet = state.carnet_mut(carnet_id)?;
+        carnet.status = CarnetStatus::Canceled;
+        for charge in &mut carnet.charges {
+            charge.status = BillingChargeStatus::Canceled;
+        }
+        Ok(carnet_response(carnet))
+    }
+
+    async fn carnet_cancel_parcel(
+        &self,
+        carnet_id: i64,
+        parcel: i32,
+    ) -> Result<CarnetResponse, Error> {
+        let mut state = self.state();
+        state.carnet_parcel_mut(carnet_id, parcel)?.status = BillingChargeStatus::Canceled;
+        Ok(carnet_response(state.carnet_mut(carnet_id)?))
+    }
+
+    async fn carnet_resend(&self, carnet_id: i64) -> Result<CarnetResponse, Error> {
+        Ok(carnet_response(self.state().carnet_mut(carnet_id)?))
+    }
+
+    async fn carnet_resend_parcel(
+        &self,
+        carnet_id: i64,
+        parcel: i32,
+    ) -> Result<CarnetResponse, Error> {
+        let mut state = self.state();
+        state.carnet_parcel_mut(carnet_id, parcel)?;
+        Ok(carnet_response(state.carnet_mut(carnet_id)?))
+    }
+
+    async fn carnet_add_history(
+        &self,
+        carnet_id: i64,
+        payload: &CarnetHistoryRequest,
+    ) -> Result<CarnetResponse, Error> {
+        let mut state = self.state();
+        let carnet = state.carnet_mut(carnet_id)?;
+        carnet.history.push(history_entry(&payload.description));
+        Ok(carnet_response(carnet))
+    }
+
+    async fn carnet_settle(&self, carnet_id: i64) -> Result<CarnetResponse, Error> {
+        let mut state = self.state();
+        let carnet = state.carnet_mut(carnet_id)?;
+        carnet.status = CarnetStatus::Settled;
+        for charge in &mut carnet.charges {
+            charge.status = BillingChargeStatus::Settled;
+        }
+        Ok(carnet_response(carnet))
+    }
+
+    async fn carnet_settle_parcel(
+        &self,
+        carnet_id: i64,
+        parcel: i32,
+    ) -> Result<CarnetResponse, Error> {
+        let mut state = self.state();
+        state.carnet_parcel_mut(carnet_id, parcel)?.status = BillingChargeStatus::Settled;
+        Ok(carnet_response(state.carnet_mut(carnet_id)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+    use crate::pix_key::PixKey;
+    use crate::types::Devedor;
+
+    fn chave() -> PixKey {
+        PixKey::email("loja@example.com").unwrap()
+    }
+
+    fn cob(cents: i64) -> CobPayload {
+        CobPayload::builder(chave(), Brl::from_cents(cents))
+            .build()
+            .unwrap()
+    }
+
+    fn cobv(cents: i64) -> CobvPayload {
+        let devedor = Devedor::Fisica {
+            cpf: "52998224725".parse().unwrap(),
+            nome: "Fulano de Tal".to_owned(),
+        };
+        CobvPayload::builder(chave(), Brl::from_cents(cents), fake_expire_at())
+            .devedor(devedor)
+            .build()
+            .unwrap()
+    }
+
+    fn pix(key: &str) -> PixTransactionPayload {
+        PixTransactionPayload::new(
+            Brl::from_cents(1_000),
+            chave(),
+            PixKey::email("fornecedor@example.com").unwrap().into(),
+        )
+        .idempotency_key(key)
+    }
+
+    fn day() -> CobListQuery {
+        CobListQuery::new(
+            "2024-01-01T00:00:00Z".parse().unwrap(),
+            "2024-01-01T23:59:59Z".parse().unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn cob_create_and_patch() {
+        let fake = FakeEfi::new();
+        let created = fake.cob_create(&cob(1_000)).await.unwrap();
+        assert_eq!(created.status, Some(CobStatus::Ativa));
+        assert_eq!(created.revisao, Some(0));
+
+        let patched = fake.cob_patch(&created.txid, &cob(2_000)).await.unwrap();
+        assert_eq!(patched.revisao, Some(1));
+        assert_eq!(patched.valor.unwrap().original, Brl::from_cents(2_000));
+        assert_eq!(patched.loc.map(|loc| loc.id), created.loc.map(|loc| loc.id));
+    }
+
+    #[tokio::test]
+    async fn cob_update_rejects_txid_in_use() {
+        let fake = FakeEfi::new();
+        let txid = Txid::from_seed("pedido-1");
+        fake.cob_update(txid.as_str(), &cob(1_000)).await.unwrap();
+
+        let error = fake
+            .cob_update(txid.as_str(), &cob(2_000))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), Some(ErrorKind::Conflict));
+        let error = fake.cob_patch("curto", &cob(2_000)).await.unwrap_err();
+        assert!(matches!(error, Error::InvalidValue { kind: "txid", .. }));
+    }
+
+    #[tokio::test]
+    async fn pay_cob_records_received_pix() {
+        let fake = FakeEfi::new();
+        let created = fake.cob_create(&cob(1_000)).await.unwrap();
+        let paid = fake.pay_cob(&created.txid).unwrap();
+        assert_eq!(paid.status, Some(CobStatus::Concluida));
+
+        let pix = &paid.pix.unwrap()[0];
+        assert_eq!(pix.txid.as_deref(), Some(created.txid.as_str()));
+        assert_eq!(pix.valor, Brl::from_cents(1_000));
+        fake.pix_received_get(&pix.end_to_end_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn cobv_create_update_and_pay() {
+        let fake = FakeEfi::new();
+        let created = fake.cobv_create(&cobv(1_000)).await.unwrap();
+        let error = fake
+            .cobv_update(&created.txid, &cobv(1_000))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), Some(ErrorKind::Conflict));
+
+        let patched = fake.cobv_patch(&created.txid, &cobv(3_000)).await.unwrap();
+        assert_eq!(patched.revisao, Some(1));
+
+        let paid = fake.pay_cobv(&created.txid).unwrap();
+        assert_eq!(paid.status, Some(CobStatus::Concluida));
+        assert_eq!(paid.pix.unwrap()[0].valor, Brl::from_cents(3_000));
+        assert!(fake.pay_cobv(Txid::from_seed("outro").as_str()).is_err());
+    }
+
+    #[tokio::test]
+    async fn refunds_are_limited_to_the_paid_value() {
+        let fake = FakeEfi::new();
+        let created = fake.cob_create(&cob(1_000)).await.unwrap();
+        let paid = fake.pay_cob(&created.txid).unwrap();
+        let e2e_id = &paid.pix.unwrap()[0].end_to_end_id;
+
+        let refund = DevolucaoPayload::new(Brl::from_cents(600));
+        let first = fake.pix_refund_create(e2e_id, "d1", &refund).await.unwrap();
+        let again = fake.pix_refund_create(e2e_id, "d1", &refund).await.unwrap();
+        assert_eq!(first.rtr_id, again.rtr_id);
+
+        let error = fake
+            .pix_refund_create(e2e_id, "d2", &refund)
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), Some(ErrorKind::Validation));
+        assert!(error.body().unwrap().contains("excede o valor disponível"));
+
+        let rest = DevolucaoPayload::new(Brl::from_cents(400));
+        fake.pix_refund_create(e2e_id, "d2", &rest).await.unwrap();
+        let cob = fake.cob_get(&created.txid).await.unwrap();
+        assert_eq!(cob.pix.unwrap()[0].devolucoes.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn cob_list_paginates() {
+        let fake = FakeEfi::new();
+        for _ in 0..5 {
+            fake.cob_create(&cob(1_000)).await.unwrap();
+        }
+
+        let page = fake
+            .cob_list(&day().itens_por_pagina(2).pagina_atual(2))
+            .await
+            .unwrap();
+        assert_eq!(page.cobs.len(), 1);
+        assert_eq!(page.parametros.paginacao.quantidade_de_paginas, 3);
+        assert_eq!(page.parametros.paginacao.quantidade_total_de_itens, 5);
+
+        let query = day().status(CobStatus::Concluida);
+        assert!(fake.cob_list(&query).await.unwrap().cobs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn pix_send_is_idempotent_by_id_envio() {
+        let fake = FakeEfi::new();
+        let first = fake.pix_send(&pix("envio1")).await.unwrap();
+        let again = fake.pix_send(&pix("envio1")).await.unwrap();
+        let other = fake.pix_send(&pix("envio2")).await.unwrap();
+
+        assert_eq!(first.end_to_end_id, again.end_to_end_id);
+        assert_ne!(first.end_to_end_id, other.end_to_end_id);
+        assert_eq!(fake.pix_transactions().len(), 2);
+        let found = fake
+            .pix_get_transaction_by_id_envio("envio1")
+            .await
+            .unwrap();
+        assert_eq!(found.end_to_end_id, first.end_to_end_id);
+    }
+}
@@ -1,31 +1,80 @@
+pub mod api;
 pub mod auth;
 pub mod billing;
+pub mod brcode;
+pub mod builders;
 pub mod carnet;
 pub mod client;
 pub mod cob;
 pub mod cobv;
+pub mod datetime;
+pub mod document;
 pub mod environment;
 pub mod error;
+pub mod fake;
+pub mod money;
+mod pagination;
 pub mod pix;
+pub mod pix_key;
+#[cfg(feature = "qrcode")]
+pub mod qr;
+pub mod retry;
+#[cfg(feature = "simulator")]
+pub mod simulator;
 pub mod split;
+pub mod status;
+#[cfg(test)]
+mod test_server;
+pub mod txid;
 pub mod types;
 pub mod webhooks;
 
+pub use api::{
+    BillingApi, CarnetApi, EfiApi, PixChargesApi, PixTransfersApi, SplitApi, WebhooksApi,
+};
+pub use brcode::{BrCode, BrCodeError, PixMerchantAccount, PointOfInitiation, StaticBrCode};
+pub use builders::{
+    BillingChargeCreateRequestBuilder, BillingChargeOneStepRequestBuilder,
+    CarnetCreateRequestBuilder, CobPayloadBuilder, CobvPayloadBuilder,
+};
 pub use client::{Client, ClientBuilder};
+pub use datetime::{Date, Timestamp};
+pub use document::{Cnpj, Cpf};
 pub use environment::{Endpoints, Environment, PRODUCTION_ENDPOINTS, SANDBOX_ENDPOINTS};
-pub use error::Error;
+pub use error::{
+    BillingErrorDescription, BillingProblem, Error, ErrorKind, PixProblem, PixViolacao,
+};
+pub use fake::FakeEfi;
+pub use money::Brl;
+pub use pix_key::{PixKey, PixKeyKind};
+#[cfg(feature = "qrcode")]
+pub use qr::{ErrorCorrection, PixQrCode};
+pub use retry::{AttemptOutcome, RetryAttempt, RetryPolicy};
+#[cfg(feature = "simulator")]
+pub use simulator::Simulator;
+pub use status::{
+    BillingChargeStatus, CarnetStatus, CobStatus, DevolucaoStatus, PixTransferStatus,
+};
+pub use txid::Txid;
 pub use types::{
     BillingAddress, BillingBankingBillet, BillingBilletConfigurations, BillingChargeCreateRequest,
     BillingChargeData, BillingChargeDetailBankingBillet, BillingChargeDetailData,
     BillingChargeDetailPayment, BillingChargeDetailResponse, BillingChargeHistoryEntry,
     BillingChargeOneStepRequest, BillingChargePayRequest, BillingChargeResponse,
-    BillingChargeShipping, BillingCreditCard, BillingCustomer, BillingItem, BillingMarketplace,
-    BillingNotificationResponse, BillingPayment, BillingPdf, BillingPix, BillingRepasse,
-    BillingShipping, CarnetCharge, CarnetChargePdf, CarnetCreateRequest, CarnetCustomer,
-    CarnetData, CarnetHistoryRequest, CarnetMetadataRequest, CarnetParcel, CarnetParcelRequest,
-    CarnetParcelsRequest, CarnetPdf, CarnetResponse, CobCalendario, CobInfoAdicional, CobPayload,
-    CobPessoa, CobResponse, CobValor, CobvCalendario, CobvPayload, CobvResponse,
-    PixTransactionDetailResponse, PixTransactionPayload, PixTransactionResponse, SplitConfig,
-    SplitConfigResponse, SplitFavorecido, SplitLancamento, SplitLinkResponse, SplitParte,
-    SplitPayload, SplitRepasse, WebhookPayload, WebhookResponse, WebhooksListResponse,
+    BillingChargeShipping, BillingCreditCard, BillingCustomer, BillingCustomerResponse,
+    BillingItem, BillingJuridicalPersonResponse, BillingMarketplace, BillingNotificationResponse,
+    BillingPayment, BillingPdf, BillingPix, BillingRepasse, BillingShipping, CarnetCharge,
+    CarnetChargePdf, CarnetCreateRequest, CarnetCustomer, CarnetData, CarnetHistoryRequest,
+    CarnetMetadataRequest, CarnetParcel, CarnetParcelRequest, CarnetParcelsRequest, CarnetPdf,
+    CarnetResponse, CobCalendario, CobInfoAdicional, CobListQuery, CobListResponse, CobLoc,
+    CobPayload, CobResponse, CobValor, CobvCalendario, CobvDevedor, CobvDevedorResponse,
+    CobvListResponse, CobvPayload, CobvResponse, ComponenteValor, ComponentesValor, ContaBanco,
+    Devedor, DevedorResponse, Devolucao, DevolucaoHorario, DevolucaoPayload, ListParametros,
+    NaturezaDevolucao, Paginacao, PixEnviadoContaBanco, PixEnviadoFavorecido, PixEnviadoListQuery,
+    PixEnviadoListResponse, PixEnviadoPagador, PixEnvioHorario, PixEnvioPagador, PixFavorecido,
+    PixGnExtras, PixIdentificacao, PixPagador, PixRecebido, PixRecebidoListQuery,
+    PixRecebidoListResponse, PixTransactionDetailResponse, PixTransactionPayload,
+    PixTransactionResponse, SplitConfig, SplitConfigResponse, SplitFavorecido, SplitLancamento,
+    SplitLinkResponse, SplitParte, SplitPayload, SplitRepasse, TipoCob, TipoConta, WebhookPayload,
+    WebhookResponse, WebhooksListResponse,
 };
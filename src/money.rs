@@ -0,0 +1,273 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::Error;
+
+/// Amount in Brazilian reais, stored as integer cents.
+///
+/// Serializes as a two-decimal string (`"10.50"`), which is what the Pix API
+/// expects. Billing API fields, which carry integer cents, use
+/// `#[serde(with = "crate::money::cents")]` instead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Brl(i64);
+
+impl Brl {
+    pub const ZERO: Self = Self(0);
+
+    #[must_use]
+    pub const fn from_cents(cents: i64) -> Self {
+        Self(cents)
+    }
+
+    #[must_use]
+    pub const fn from_reais(reais: i64) -> Option<Self> {
+        match reais.checked_mul(100) {
+            Some(cents) => Some(Self(cents)),
+            None => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn cents(self) -> i64 {
+        self.0
+    }
+
+    #[must_use]
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    #[must_use]
+    pub const fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    #[must_use]
+    pub const fn checked_add(self, other: Self) -> Option<Self> {
+        match self.0.checked_add(other.0) {
+            Some(cents) => Some(Self(cents)),
+            None => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn checked_sub(self, other: Self) -> Option<Self> {
+        match self.0.checked_sub(other.0) {
+            Some(cents) => Some(Self(cents)),
+            None => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn checked_mul(self, quantity: i64) -> Option<Self> {
+        match self.0.checked_mul(quantity) {
+            Some(cents) => Some(Self(cents)),
+            None => None,
+        }
+    }
+
+    /// Sums `amounts`, returning `None` on overflow.
+    pub fn checked_sum(amounts: impl IntoIterator<Item = Self>) -> Option<Self> {
+        amounts
+            .into_iter()
+            .try_fold(Self::ZERO, |total, amount| total.checked_add(amount))
+    }
+}
+
+impl Display for Brl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let cents = self.0.unsigned_abs();
+        write!(f, "{sign}{}.{:02}", cents / 100, cents % 100)
+    }
+}
+
+impl FromStr for Brl {
+    type Err = Error;
+
+    /// Parses `"10"`, `"10.5"` or `"10.50"`; more than two decimal places is rejected.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        const fn invalid(reason: &'static str) -> Error {
+            Error::InvalidValue {
+                kind: "brl amount",
+                reason,
+            }
+        }
+
+        let (negative, unsigned) = match value.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, value),
+        };
+        let (reais, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+
+        if reais.is_empty() || !reais.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid("expected digits before the decimal point"));
+        }
+        if !fraction.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid("expected digits after the decimal point"));
+        }
+        if fraction.len() > 2 || (unsigned.contains('.') && fraction.is_empty()) {
+            return Err(invalid("expected one or two decimal places"));
+        }
+
+        let overflow = || invalid("amount out of range");
+        // Widened so the sign is applied before the range check, which lets
+        // the most negative amount round-trip through `Display`.
+        let reais: i128 = reais.parse().map_err(|_| overflow())?;
+        let fraction: i128 = match fraction.len() {
+            0 => 0,
+            1 => fraction.parse::<i128>().map_err(|_| overflow())? * 10,
+            _ => fraction.parse().map_err(|_| overflow())?,
+        };
+
+        let cents = reais
+            .checked_mul(100)
+            .and_then(|cents| cents.checked_add(fraction))
+            .ok_or_else(overflow)?;
+        let cents = if negative { -cents } else { cents };
+
+        i64::try_from(cents).map(Self).map_err(|_| overflow())
+    }
+}
+
+impl Serialize for Brl {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Brl {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Serde adapter for Billing API amounts, which are integer cents.
+pub mod cents {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::Brl;
+
+    pub fn serialize<S: Serializer>(value: &Brl, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(value.cents())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Brl, D::Error> {
+        i64::deserialize(deserializer).map(Brl::from_cents)
+    }
+
+    pub mod option {
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        use super::Brl;
+
+        pub fn serialize<S: Serializer>(
+            value: &Option<Brl>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match value {
+                Some(value) => serializer.serialize_some(&value.cents()),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Brl>, D::Error> {
+            Option::<i64>::deserialize(deserializer).map(|cents| cents.map(Brl::from_cents))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Brl;
+
+    #[test]
+    fn parses_one_or_two_decimal_places() {
+        assert_eq!("10".parse::<Brl>().unwrap(), Brl::from_cents(1000));
+        assert_eq!("10.5".parse::<Brl>().unwrap(), Brl::from_cents(1050));
+        assert_eq!("10.05".parse::<Brl>().unwrap(), Brl::from_cents(1005));
+        assert_eq!("0.01".parse::<Brl>().unwrap(), Brl::from_cents(1));
+        assert_eq!("-0.01".parse::<Brl>().unwrap(), Brl::from_cents(-1));
+    }
+
+    #[test]
+    fn rejects_amounts_that_would_need_rounding() {
+        for value in ["10.505", "0.001", "10.", ".5", "+1", "1e3", "1,50", "", "-"] {
+            assert!(
+                value.parse::<Brl>().is_err(),
+                "{value:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn displays_two_decimal_places() {
+        assert_eq!(Brl::from_cents(1050).to_string(), "10.50");
+        assert_eq!(Brl::from_cents(7).to_string(), "0.07");
+        assert_eq!(Brl::from_cents(-7).to_string(), "-0.07");
+        assert_eq!(Brl::ZERO.to_string(), "0.00");
+    }
+
+    #[test]
+    fn round_trips_the_extremes() {
+        for amount in [Brl::from_cents(i64::MAX), Brl::from_cents(i64::MIN)] {
+            assert_eq!(amount.to_string().parse::<Brl>().unwrap(), amount);
+        }
+        assert_eq!(
+            Brl::from_cents(i64::MAX).to_string(),
+            "92233720368547758.07"
+        );
+    }
+
+    #[test]
+    fn rejects_out_of_range_strings() {
+        assert!("92233720368547758.08".parse::<Brl>().is_err());
+        assert!("-92233720368547758.09".parse::<Brl>().is_err());
+        assert!(
+            "100000000000000000000000000000000000000000"
+                .parse::<Brl>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn checked_arithmetic_reports_overflow() {
+        let max = Brl::from_cents(i64::MAX);
+        assert_eq!(max.checked_add(Brl::from_cents(1)), None);
+        assert_eq!(
+            Brl::from_cents(i64::MIN).checked_sub(Brl::from_cents(1)),
+            None
+        );
+        assert_eq!(max.checked_mul(2), None);
+        assert_eq!(Brl::checked_sum([max, Brl::from_cents(1)]), None);
+        assert_eq!(
+            Brl::checked_sum([Brl::from_cents(150), Brl::from_cents(250)]),
+            Some(Brl::from_cents(400))
+        );
+        assert_eq!(Brl::from_reais(i64::MAX / 100 + 1), None);
+        assert_eq!(Brl::from_reais(3), Some(Brl::from_cents(300)));
+    }
+
+    #[test]
+    fn serializes_as_string_or_cents() {
+        let amount = Brl::from_cents(1050);
+        assert_eq!(serde_json::to_string(&amount).unwrap(), r#""10.50""#);
+
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Billing {
+            #[serde(with = "crate::money::cents")]
+            value: Brl,
+        }
+        let billing: Billing = serde_json::from_str(r#"{"value":1050}"#).unwrap();
+        assert_eq!(billing.value, amount);
+        assert_eq!(
+            serde_json::to_string(&billing).unwrap(),
+            r#"{"value":1050}"#
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use std::future::Future;
+
+use futures_util::stream::{self, Stream, StreamExt};
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::types::Paginacao;
+
+/// Streams the items of every page, starting at `first_page` and fetching the
+/// next one only once the previous page has been consumed. An error is yielded
+/// once and ends the stream.
+pub(crate) fn paginate<'a, T, F, Fut>(
+    first_page: u32,
+    fetch: F,
+) -> impl Stream<Item = Result<T, Error>> + Send + 'a
+where
+    T: Send + 'a,
+    F: FnMut(u32) -> Fut + Send + 'a,
+    Fut: Future<Output = Result<(Vec<T>, Paginacao), Error>> + Send + 'a,
+{
+    stream::unfold((Some(first_page), fetch), |(page, mut fetch)| async move {
+        let page = page?;
+        match fetch(page).await {
+            Ok((items, paginacao)) => {
+                let next = paginacao
+                    .has_next_page()
+                    .then(|| paginacao.pagina_atual + 1);
+                Some((Ok(items), (next, fetch)))
+            }
+            Err(error) => Some((Err(error), (None, fetch))),
+        }
+    })
+    .flat_map(|page| {
+        let items: Vec<Result<T, Error>> = match page {
+            Ok(items) => items.into_iter().map(Ok).collect(),
+            Err(error) => vec![Err(error)],
+        };
+        stream::iter(items)
+    })
+}
+
+/// `path?query`, with the query URL-encoded from its `Serialize` impl.
+pub(crate) fn with_query<Q: Serialize>(path: &str, query: &Q) -> Result<String, Error> {
+    let query = serde_urlencoded::to_string(query).map_err(|_| Error::InvalidValue {
+        kind: "query string",
+        reason: "cannot be URL-encoded",
+    })?;
+    Ok(if query.is_empty() {
+        path.to_owned()
+    } else {
+        format!("{path}?{query}")
+    })
+}
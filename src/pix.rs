@@ -1,15 +1,27 @@
+use futures_util::stream::BoxStream;
 use reqwest::Method;
 
+use crate::api::PixTransfersApi;
 use crate::client::Client;
 use crate::error::Error;
-use crate::types::{PixTransactionDetailResponse, PixTransactionPayload, PixTransactionResponse};
+use crate::pagination::with_query;
+use crate::types::{
+    Devolucao, DevolucaoPayload, PixEnviadoListQuery, PixEnviadoListResponse, PixRecebido,
+    PixRecebidoListQuery, PixRecebidoListResponse, PixTransactionDetailResponse,
+    PixTransactionPayload, PixTransactionResponse,
+};
 
 impl Client {
+    /// Sends a Pix through `PUT /v3/gn/pix/{idEnvio}`, with the `idEnvio` from
+    /// [`PixTransactionPayload::id_envio`]. Sending the same payload again,
+    /// or retrying, reuses it.
     pub async fn pix_send(
         &self,
         payload: &PixTransactionPayload,
     ) -> Result<PixTransactionResponse, Error> {
-        self.send_authenticated(Method::POST, "/v2/pix/send", Some(payload))
+        let id_envio = payload.id_envio();
+        let path = format!("/v3/gn/pix/{id_envio}");
+        self.send_authenticated(Method::PUT, &path, Some(payload))
             .await
     }
 
@@ -17,7 +29,8 @@ impl Client {
         &self,
         end_to_end_id: &str,
     ) -> Result<PixTransactionDetailResponse, Error> {
-        let path = format!("/v2/pix/transaction/{end_to_end_id}");
+        check_id("end-to-end id", end_to_end_id)?;
+        let path = format!("/v2/gn/pix/enviados/{end_to_end_id}");
         self.send_authenticated::<serde_json::Value, PixTransactionDetailResponse>(
             Method::GET,
             &path,
@@ -25,4 +38,150 @@ impl Client {
         )
         .await
     }
+
+    /// Looks a transfer up by the `idEnvio` it was sent with, which is known
+    /// even when `pix_send` timed out before returning the end-to-end id.
+    pub async fn pix_get_transaction_by_id_envio(
+        &self,
+        id_envio: &str,
+    ) -> Result<PixTransactionDetailResponse, Error> {
+        check_id("idEnvio", id_envio)?;
+        let path = format!("/v2/gn/pix/enviados/id-envio/{id_envio}");
+        self.send_authenticated::<serde_json::Value, PixTransactionDetailResponse>(
+            Method::GET,
+            &path,
+            None,
+        )
+        .await
+    }
+
+    pub async fn pix_sent_list(
+        &self,
+        query: &PixEnviadoListQuery,
+    ) -> Result<PixEnviadoListResponse, Error> {
+        let path = with_query("/v2/gn/pix/enviados", query)?;
+        self.send_authenticated::<serde_json::Value, PixEnviadoListResponse>(
+            Method::GET,
+            &path,
+            None,
+        )
+        .await
+    }
+
+    /// Every sent Pix matching `query`, fetching one page at a time as the
+    /// stream is polled. Starts at `query.pagina_atual`, or the first page.
+    pub fn pix_sent_list_stream(
+        &self,
+        query: PixEnviadoListQuery,
+    ) -> BoxStream<'_, Result<PixTransactionDetailResponse, Error>> {
+        PixTransfersApi::pix_sent_list_stream(self, query)
+    }
+
+    /// Pix received on the account's keys, whether or not they paid a charge.
+    pub async fn pix_received_list(
+        &self,
+        query: &PixRecebidoListQuery,
+    ) -> Result<PixRecebidoListResponse, Error> {
+        let path = with_query("/v2/pix", query)?;
+        self.send_authenticated::<serde_json::Value, PixRecebidoListResponse>(
+            Method::GET,
+            &path,
+            None,
+        )
+        .await
+    }
+
+    /// Every Pix matching `query`, fetching one page at a time as the stream
+    /// is polled. Starts at `query.pagina_atual`, or the first page.
+    pub fn pix_received_list_stream(
+        &self,
+        query: PixRecebidoListQuery,
+    ) -> BoxStream<'_, Result<PixRecebido, Error>> {
+        PixTransfersApi::pix_received_list_stream(self, query)
+    }
+
+    pub async fn pix_received_get(&self, end_to_end_id: &str) -> Result<PixRecebido, Error> {
+        check_id("end-to-end id", end_to_end_id)?;
+        let path = format!("/v2/pix/{end_to_end_id}");
+        self.send_authenticated::<serde_json::Value, PixRecebido>(Method::GET, &path, None)
+            .await
+    }
+
+    /// Refunds all or part of a received Pix. `refund_id` (1 to 35 letters or
+    /// digits) is chosen by the caller and identifies the refund, so retrying
+    /// with the same id cannot refund twice.
+    pub async fn pix_refund_create(
+        &self,
+        end_to_end_id: &str,
+        refund_id: &str,
+        payload: &DevolucaoPayload,
+    ) -> Result<Devolucao, Error> {
+        check_id("end-to-end id", end_to_end_id)?;
+        check_id("refund id", refund_id)?;
+        let path = format!("/v2/pix/{end_to_end_id}/devolucao/{refund_id}");
+        self.send_authenticated(Method::PUT, &path, Some(payload))
+            .await
+    }
+
+    pub async fn pix_refund_get(
+        &self,
+        end_to_end_id: &str,
+        refund_id: &str,
+    ) -> Result<Devolucao, Error> {
+        check_id("end-to-end id", end_to_end_id)?;
+        check_id("refund id", refund_id)?;
+        let path = format!("/v2/pix/{end_to_end_id}/devolucao/{refund_id}");
+        self.send_authenticated::<serde_json::Value, Devolucao>(Method::GET, &path, None)
+            .await
+    }
+}
+
+/// Checks an identifier that goes into a URL path: end-to-end ids, `idEnvio`
+/// and refund ids are all 1 to 35 letters or digits.
+pub(crate) fn check_id(kind: &'static str, value: &str) -> Result<(), Error> {
+    if (1..=35).contains(&value.len()) && value.bytes().all(|b| b.is_ascii_alphanumeric()) {
+        Ok(())
+    } else {
+        Err(Error::InvalidValue {
+            kind,
+            reason: "must be 1 to 35 letters or digits",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_id;
+    use crate::error::Error;
+
+    #[test]
+    fn accepts_path_ids() {
+        for value in ["E09089356202401011200abcdefghijk", "a", &"9".repeat(35)] {
+            assert!(check_id("end-to-end id", value).is_ok(), "{value:?}");
+        }
+    }
+
+    #[test]
+    fn rejects_ids_that_would_change_the_path() {
+        for value in [
+            "",
+            "../cob",
+            "abc/devolucao/1",
+            "abc?x=1",
+            "E0908 93",
+            &"9".repeat(36),
+        ] {
+            let error = check_id("idEnvio", value).unwrap_err();
+            assert!(
+                matches!(
+                    error,
+                    Error::InvalidValue {
+                        kind: "idEnvio",
+                        ..
+                    }
+                ),
+                "{value:?}"
+            );
+        }
+    }
 }
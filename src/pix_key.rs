@@ -0,0 +1,292 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::document::{Cnpj, Cpf, strip_punctuation};
+use crate::error::Error;
+
+/// Pix key (chave) in the normalized form the DICT stores it.
+///
+/// [`PixKey::parse`] detects the kind; the typed constructors accept only
+/// their own kind. Serializes as the plain key string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PixKey {
+    Cpf(Cpf),
+    Cnpj(Cnpj),
+    /// Lowercased address.
+    Email(String),
+    /// `+55` followed by area code and number.
+    Phone(String),
+    /// Random key (UUID), lowercased.
+    Evp(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PixKeyKind {
+    Cpf,
+    Cnpj,
+    Email,
+    Phone,
+    Evp,
+}
+
+const fn invalid(reason: &'static str) -> Error {
+    Error::InvalidValue {
+        kind: "pix key",
+        reason,
+    }
+}
+
+impl PixKey {
+    /// Detects the key kind: `@` means email, a leading `+` means phone, a
+    /// UUID means EVP, and 11 or 14 characters (punctuation allowed) mean CPF
+    /// or CNPJ.
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        let value = value.trim();
+        if value.contains('@') {
+            Self::email(value)
+        } else if value.starts_with('+') {
+            Self::phone(value)
+        } else if is_uuid(value) {
+            Self::evp(value)
+        } else {
+            match strip_punctuation(value).map(|chars| chars.len()) {
+                Some(11) => Self::cpf(value),
+                Some(14) => Self::cnpj(value),
+                _ => Err(invalid("not a CPF, CNPJ, email, phone or EVP key")),
+            }
+        }
+    }
+
+    /// Accepts `12345678909` or `123.456.789-09`.
+    pub fn cpf(value: &str) -> Result<Self, Error> {
+        Cpf::parse(value).map(Self::Cpf)
+    }
+
+    /// Accepts `11222333000181`, `11.222.333/0001-81` or an alphanumeric CNPJ.
+    pub fn cnpj(value: &str) -> Result<Self, Error> {
+        Cnpj::parse(value).map(Self::Cnpj)
+    }
+
+    pub fn email(value: &str) -> Result<Self, Error> {
+        let value = value.trim().to_lowercase();
+        if value.len() > 77 {
+            return Err(invalid("email must have at most 77 characters"));
+        }
+        let Some((local, domain)) = value.split_once('@') else {
+            return Err(invalid("email must contain @"));
+        };
+        let valid_local = !local.is_empty()
+            && !local.starts_with('.')
+            && !local.ends_with('.')
+            && local
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+/=?^_`{|}~.-".contains(c));
+        let valid_domain = domain.contains('.')
+            && domain.split('.').all(|label| {
+                !label.is_empty()
+                    && !label.starts_with('-')
+                    && !label.ends_with('-')
+                    && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            });
+        if !valid_local || !valid_domain {
+            return Err(invalid("malformed email address"));
+        }
+        Ok(Self::Email(value))
+    }
+
+    /// Accepts `+5511987654321`; spaces, dashes and parentheses are removed.
+    pub fn phone(value: &str) -> Result<Self, Error> {
+        let value: String = value
+            .trim()
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '-' | '(' | ')'))
+            .collect();
+        let Some(national) = value.strip_prefix("+55") else {
+            return Err(invalid("phone must start with +55"));
+        };
+        if !matches!(national.len(), 10 | 11) || !national.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid(
+                "phone must have an area code and 8 or 9 digits after +55",
+            ));
+        }
+        if national.starts_with('0') {
+            return Err(invalid("phone area code must not start with 0"));
+        }
+        Ok(Self::Phone(value))
+    }
+
+    /// Accepts a UUID in the `8-4-4-4-12` hexadecimal layout.
+    pub fn evp(value: &str) -> Result<Self, Error> {
+        let value = value.trim();
+        if !is_uuid(value) {
+            return Err(invalid("EVP must be a UUID"));
+        }
+        Ok(Self::Evp(value.to_ascii_lowercase()))
+    }
+
+    #[must_use]
+    pub const fn kind(&self) -> PixKeyKind {
+        match self {
+            Self::Cpf(_) => PixKeyKind::Cpf,
+            Self::Cnpj(_) => PixKeyKind::Cnpj,
+            Self::Email(_) => PixKeyKind::Email,
+            Self::Phone(_) => PixKeyKind::Phone,
+            Self::Evp(_) => PixKeyKind::Evp,
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Cpf(cpf) => cpf.as_str(),
+            Self::Cnpj(cnpj) => cnpj.as_str(),
+            Self::Email(value) | Self::Phone(value) | Self::Evp(value) => value,
+        }
+    }
+}
+
+fn is_uuid(value: &str) -> bool {
+    let groups: Vec<&str> = value.split('-').collect();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(group, len)| group.len() == len && group.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+impl Display for PixKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PixKey {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+impl Serialize for PixKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for PixKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PixKey, PixKeyKind};
+
+    fn kind(value: &str) -> PixKeyKind {
+        PixKey::parse(value).unwrap().kind()
+    }
+
+    #[test]
+    fn detects_each_kind() {
+        assert_eq!(kind("529.982.247-25"), PixKeyKind::Cpf);
+        assert_eq!(kind("11.222.333/0001-81"), PixKeyKind::Cnpj);
+        assert_eq!(kind("12.ABC.345/01DE-35"), PixKeyKind::Cnpj);
+        assert_eq!(kind(" Fulano@Example.com "), PixKeyKind::Email);
+        assert_eq!(kind("+55 (11) 98765-4321"), PixKeyKind::Phone);
+        assert_eq!(
+            kind("123E4567-E89B-12D3-A456-426614174000"),
+            PixKeyKind::Evp
+        );
+    }
+
+    #[test]
+    fn eleven_digits_without_plus_are_a_cpf() {
+        assert_eq!(
+            PixKey::parse("52998224725").unwrap(),
+            PixKey::cpf("52998224725").unwrap()
+        );
+        assert_eq!(
+            PixKey::parse("+5552998224725").unwrap(),
+            PixKey::Phone("+5552998224725".to_owned())
+        );
+        // A mobile number without `+55` is read as a CPF and fails its check digits.
+        assert!(PixKey::parse("11987654321").is_err());
+        assert!(PixKey::phone("11987654321").is_err());
+    }
+
+    #[test]
+    fn digit_only_uuid_is_an_evp() {
+        let key = PixKey::parse("12345678-1234-1234-1234-123456789012").unwrap();
+        assert_eq!(key.kind(), PixKeyKind::Evp);
+        assert!(PixKey::cpf("12345678-1234-1234-1234-123456789012").is_err());
+    }
+
+    #[test]
+    fn normalizes_keys() {
+        assert_eq!(
+            PixKey::parse("529.982.247-25").unwrap().as_str(),
+            "52998224725"
+        );
+        assert_eq!(
+            PixKey::email(" Fulano@Example.COM ").unwrap().as_str(),
+            "fulano@example.com"
+        );
+        assert_eq!(
+            PixKey::phone("+55 11 3333-4444").unwrap().as_str(),
+            "+551133334444"
+        );
+        assert_eq!(
+            PixKey::evp("123E4567-E89B-12D3-A456-426614174000")
+                .unwrap()
+                .as_str(),
+            "123e4567-e89b-12d3-a456-426614174000"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_keys() {
+        for value in [
+            "",
+            "fulano",
+            "529.982.247-52",
+            "1234567890123",
+            "+1 555 123 4567",
+            "+55 11 9876",
+            "+55 01 98765-4321",
+            "+55 11 98765-432a",
+            "@example.com",
+            "fulano@",
+            "fulano@example",
+            ".fulano@example.com",
+            "fulano.@example.com",
+            "fulano@-example.com",
+            "ful ano@example.com",
+        ] {
+            assert!(PixKey::parse(value).is_err(), "{value:?} should be invalid");
+        }
+        let long = format!("{}@example.com", "a".repeat(66));
+        assert!(PixKey::email(&long).is_err());
+    }
+
+    #[test]
+    fn typed_constructors_reject_other_kinds() {
+        assert!(PixKey::email("+5511987654321").is_err());
+        assert!(PixKey::phone("fulano@example.com").is_err());
+        assert!(PixKey::evp("52998224725").is_err());
+        assert!(PixKey::evp("123e4567-e89b-12d3-a456-42661417400g").is_err());
+        assert!(PixKey::evp("123e4567e89b12d3a456426614174000").is_err());
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let key: PixKey = serde_json::from_str(r#""+5511987654321""#).unwrap();
+        assert_eq!(key.kind(), PixKeyKind::Phone);
+        assert_eq!(serde_json::to_string(&key).unwrap(), r#""+5511987654321""#);
+        assert!(serde_json::from_str::<PixKey>(r#""not a key""#).is_err());
+    }
+}
@@ -0,0 +1,210 @@
+use std::fmt::Write as _;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use qrcode::types::QrError;
+use qrcode::{Color, EcLevel, QrCode};
+
+use crate::error::Error;
+
+/// Renders a BR Code string (`CobResponse.pix_copia_e_cola`, `BillingPix.qrcode`, or the
+/// output of `StaticBrCode::build`) into a scannable QR code image.
+#[derive(Debug, Clone)]
+pub struct PixQrCode {
+    payload: String,
+    module_size: u32,
+    quiet_zone: u32,
+    error_correction: ErrorCorrection,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ErrorCorrection {
+    Low,
+    #[default]
+    Medium,
+    Quartile,
+    High,
+}
+
+/// Widest PNG `to_png` renders, in pixels; the pixel buffer is side squared.
+const MAX_PNG_SIDE: u32 = 8192;
+
+/// Black modules plus the quiet zone, ready to be drawn.
+struct Matrix {
+    colors: Vec<Color>,
+    width: u32,
+    quiet_zone: u32,
+}
+
+impl PixQrCode {
+    #[must_use]
+    pub fn new(payload: impl Into<String>) -> Self {
+        Self {
+            payload: payload.into(),
+            module_size: 8,
+            quiet_zone: 4,
+            error_correction: ErrorCorrection::default(),
+        }
+    }
+
+    /// Side of each module, in pixels (PNG) or user units (SVG).
+    #[must_use]
+    pub fn module_size(mut self, value: u32) -> Self {
+        self.module_size = value.max(1);
+        self
+    }
+
+    /// Blank border around the code, in modules. Scanners expect at least 4.
+    #[must_use]
+    pub const fn quiet_zone(mut self, value: u32) -> Self {
+        self.quiet_zone = value;
+        self
+    }
+
+    #[must_use]
+    pub const fn error_correction(mut self, value: ErrorCorrection) -> Self {
+        self.error_correction = value;
+        self
+    }
+
+    pub fn to_svg(&self) -> Result<String, Error> {
+        let matrix = self.matrix()?;
+        let side = self.image_side(&matrix)?;
+        let mut svg = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+             <svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" \
+             width=\"{side}\" height=\"{side}\" viewBox=\"0 0 {view} {view}\" \
+             shape-rendering=\"crispEdges\">\
+             <rect width=\"{view}\" height=\"{view}\" fill=\"#fff\"/><path fill=\"#000\" d=\"",
+            view = matrix.side(),
+        );
+        for (x, y) in matrix.dark_modules() {
+            write!(svg, "M{x} {y}h1v1h-1z").expect("writing to a String cannot fail");
+        }
+        svg.push_str("\"/></svg>");
+        Ok(svg)
+    }
+
+    /// 8-bit grayscale PNG, at most 8192 pixels wide.
+    pub fn to_png(&self) -> Result<Vec<u8>, Error> {
+        let matrix = self.matrix()?;
+        let side = self.image_side(&matrix)?;
+        if side > MAX_PNG_SIDE {
+            return Err(too_large("PNG would be wider than 8192 pixels"));
+        }
+        let scale = self.module_size as usize;
+        let row_len = side as usize;
+        let len = row_len
+            .checked_mul(row_len)
+            .ok_or_else(|| too_large("PNG does not fit in memory"))?;
+
+        let mut pixels = vec![0xFF_u8; len];
+        for (x, y) in matrix.dark_modules() {
+            for row in (y as usize * scale)..((y as usize + 1) * scale) {
+                let start = row * row_len + x as usize * scale;
+                pixels[start..start + scale].fill(0);
+            }
+        }
+
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, side, side);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&pixels))
+            .map_err(|error| Error::Io(std::io::Error::other(error)))?;
+        Ok(png)
+    }
+
+    /// `data:image/png;base64,...`, for `<img src>` in HTML and emails.
+    pub fn to_png_data_uri(&self) -> Result<String, Error> {
+        Ok(format!(
+            "data:image/png;base64,{}",
+            STANDARD.encode(self.to_png()?)
+        ))
+    }
+
+    /// `data:image/svg+xml;base64,...`, the format of `BillingPix.qrcode_image`.
+    pub fn to_svg_data_uri(&self) -> Result<String, Error> {
+        Ok(format!(
+            "data:image/svg+xml;base64,{}",
+            STANDARD.encode(self.to_svg()?)
+        ))
+    }
+
+    fn image_side(&self, matrix: &Matrix) -> Result<u32, Error> {
+        matrix
+            .quiet_zone
+            .checked_mul(2)
+            .and_then(|border| border.checked_add(matrix.width))
+            .and_then(|side| side.checked_mul(self.module_size))
+            .ok_or_else(|| too_large("module size and quiet zone are too large"))
+    }
+
+    fn matrix(&self) -> Result<Matrix, Error> {
+        let level = match self.error_correction {
+            ErrorCorrection::Low => EcLevel::L,
+            ErrorCorrection::Medium => EcLevel::M,
+            ErrorCorrection::Quartile => EcLevel::Q,
+            ErrorCorrection::High => EcLevel::H,
+        };
+        let code = QrCode::with_error_correction_level(&self.payload, level).map_err(|error| {
+            Error::InvalidValue {
+                kind: "qr code payload",
+                reason: match error {
+                    QrError::DataTooLong => "payload is too long for a QR code",
+                    _ => "payload cannot be encoded",
+                },
+            }
+        })?;
+        Ok(Matrix {
+            width: u32::try_from(code.width()).expect("QR codes are at most 177 modules wide"),
+            colors: code.to_colors(),
+            quiet_zone: self.quiet_zone,
+        })
+    }
+}
+
+const fn too_large(reason: &'static str) -> Error {
+    Error::InvalidValue {
+        kind: "qr code image",
+        reason,
+    }
+}
+
+impl Matrix {
+    const fn side(&self) -> u32 {
+        self.width + 2 * self.quiet_zone
+    }
+
+    /// Coordinates of dark modules, offset by the quiet zone.
+    fn dark_modules(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        (0..self.width)
+            .flat_map(move |y| (0..self.width).map(move |x| (x, y)))
+            .filter(|&(x, y)| self.colors[(y * self.width + x) as usize] == Color::Dark)
+            .map(|(x, y)| (x + self.quiet_zone, y + self.quiet_zone))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PixQrCode;
+    use crate::error::Error;
+
+    #[test]
+    fn rejects_oversized_png() {
+        let qr = PixQrCode::new("000201").module_size(1000);
+        assert!(matches!(qr.to_png(), Err(Error::InvalidValue { .. })));
+        assert!(qr.to_svg().is_ok());
+
+        let qr = PixQrCode::new("000201").module_size(u32::MAX);
+        assert!(matches!(qr.to_png(), Err(Error::InvalidValue { .. })));
+    }
+
+    #[test]
+    fn renders_png_within_limit() {
+        let png = PixQrCode::new("000201").to_png().unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+    }
+}
@@ -0,0 +1,445 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Method, StatusCode};
+
+use crate::error::Error;
+
+type AttemptObserver = Arc<dyn Fn(&RetryAttempt<'_>) + Send + Sync>;
+
+/// Controls how failed requests are retried.
+///
+/// Only idempotent methods (`GET`, `HEAD`, `OPTIONS`, `PUT` and `DELETE`) are
+/// retried. A request is retried on `429`, `500`, `502`, `503`, `504` and on
+/// connection failures.
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: u32,
+    jitter: bool,
+    respect_retry_after: bool,
+    on_attempt: Option<AttemptObserver>,
+}
+
+/// One finished attempt, passed to the observer set with [`RetryPolicy::on_attempt`].
+#[derive(Debug)]
+pub struct RetryAttempt<'a> {
+    pub method: &'a Method,
+    pub path: &'a str,
+    /// 1-based attempt number.
+    pub attempt: u32,
+    pub outcome: AttemptOutcome<'a>,
+    /// Delay before the next attempt, or `None` if this attempt is final.
+    pub retry_in: Option<Duration>,
+}
+
+#[derive(Debug)]
+pub enum AttemptOutcome<'a> {
+    Status(StatusCode),
+    Error(&'a Error),
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+            multiplier: 2,
+            jitter: true,
+            respect_retry_after: true,
+            on_attempt: None,
+        }
+    }
+}
+
+impl Debug for RetryPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("multiplier", &self.multiplier)
+            .field("jitter", &self.jitter)
+            .field("respect_retry_after", &self.respect_retry_after)
+            .field("on_attempt", &self.on_attempt.is_some())
+            .finish()
+    }
+}
+
+impl RetryPolicy {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A policy that sends every request exactly once.
+    #[must_use]
+    pub fn disabled() -> Self {
+        Self::default().max_attempts(1)
+    }
+
+    /// Total number of attempts, including the first one.
+    #[must_use]
+    pub fn max_attempts(mut self, value: u32) -> Self {
+        self.max_attempts = value.max(1);
+        self
+    }
+
+    #[must_use]
+    pub const fn initial_backoff(mut self, value: Duration) -> Self {
+        self.initial_backoff = value;
+        self
+    }
+
+    #[must_use]
+    pub const fn max_backoff(mut self, value: Duration) -> Self {
+        self.max_backoff = value;
+        self
+    }
+
+    #[must_use]
+    pub fn multiplier(mut self, value: u32) -> Self {
+        self.multiplier = value.max(1);
+        self
+    }
+
+    /// Randomizes each backoff between half and all of its computed value.
+    #[must_use]
+    pub const fn jitter(mut self, enabled: bool) -> Self {
+        self.jitter = enabled;
+        self
+    }
+
+    /// Waits for the delay in a `Retry-After` header, given in seconds or as an
+    /// HTTP date, instead of the computed backoff. The wait is still capped at
+    /// `max_backoff`.
+    #[must_use]
+    pub const fn respect_retry_after(mut self, enabled: bool) -> Self {
+        self.respect_retry_after = enabled;
+        self
+    }
+
+    #[must_use]
+    pub fn on_attempt(
+        mut self,
+        observer: impl Fn(&RetryAttempt<'_>) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_attempt = Some(Arc::new(observer));
+        self
+    }
+
+    pub(crate) fn applies_to(&self, method: &Method) -> bool {
+        self.max_attempts > 1
+            && matches!(
+                *method,
+                Method::GET | Method::HEAD | Method::OPTIONS | Method::PUT | Method::DELETE
+            )
+    }
+
+    pub(crate) fn retry_delay(
+        &self,
+        attempt: u32,
+        result: &Result<reqwest::Response, Error>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+
+        match result {
+            Ok(response) if is_retryable_status(response.status()) => Some(
+                self.retry_after(response)
+                    .unwrap_or_else(|| self.backoff(attempt)),
+            ),
+            Err(Error::Http(error)) if is_retryable_error(error) => Some(self.backoff(attempt)),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn observe(&self, attempt: &RetryAttempt<'_>) {
+        if let Some(observer) = &self.on_attempt {
+            observer(attempt);
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = self.multiplier.saturating_pow(attempt - 1);
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff);
+
+        if self.jitter {
+            backoff.mul_f64(fastrand::f64().mul_add(0.5, 0.5))
+        } else {
+            backoff
+        }
+    }
+
+    fn retry_after(&self, response: &reqwest::Response) -> Option<Duration> {
+        if !self.respect_retry_after {
+            return None;
+        }
+
+        let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+        let delay = match value.parse::<u64>() {
+            Ok(seconds) => Duration::from_secs(seconds),
+            Err(_) => (UNIX_EPOCH + Duration::from_secs(parse_http_date(value)?))
+                .duration_since(SystemTime::now())
+                .unwrap_or_default(),
+        };
+        Some(delay.min(self.max_backoff))
+    }
+}
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Seconds since the Unix epoch for an HTTP date in the IMF-fixdate form
+/// servers are required to send, e.g. `Sun, 06 Nov 1994 08:49:37 GMT`.
+fn parse_http_date(value: &str) -> Option<u64> {
+    let (_weekday, rest) = value.split_once(", ")?;
+    let mut parts = rest.split(' ');
+    let day = fixed_digits(parts.next()?, 2)?;
+    let month = parts.next()?;
+    let month = MONTHS.iter().position(|&name| name == month)? as u64 + 1;
+    let year = fixed_digits(parts.next()?, 4)?;
+    let mut clock = parts.next()?.split(':');
+    let hour = fixed_digits(clock.next()?, 2)?;
+    let minute = fixed_digits(clock.next()?, 2)?;
+    let second = fixed_digits(clock.next()?, 2)?;
+    if parts.next()? != "GMT" || parts.next().is_some() || clock.next().is_some() {
+        return None;
+    }
+    if year < 1970 || !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    // Days from the civil date, counting years from March so leap days fall last.
+    let year = if month <= 2 { year - 1 } else { year };
+    let year_of_era = year % 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = (year / 400 * 146_097 + day_of_era).checked_sub(719_468)?;
+
+    Some(days * 86_400 + hour * 3_600 + minute * 60 + second)
+}
+
+fn fixed_digits(value: &str, len: usize) -> Option<u64> {
+    if value.len() != len || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
+
+const fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+fn is_retryable_error(error: &reqwest::Error) -> bool {
+    error.is_connect() || error.is_timeout() || error.is_request()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use reqwest::StatusCode;
+
+    use super::{AttemptOutcome, RetryPolicy, parse_http_date};
+    use crate::money::Brl;
+    use crate::pix_key::PixKey;
+    use crate::test_server::{Reply, TestServer};
+    use crate::txid::Txid;
+    use crate::types::CobPayload;
+
+    type Attempts = Arc<Mutex<Vec<(u32, Option<Duration>)>>>;
+
+    const COB: &str = r#"{"txid":"abcdefghijklmnopqrstuvwxyz"}"#;
+
+    /// Issues tokens and answers every other request with `status` until the
+    /// `ok_after`-th hit on that path, then with `200`.
+    async fn server(status: u16, ok_after: usize) -> TestServer {
+        TestServer::start(move |_, path, hit| {
+            if path == "/oauth/token" {
+                Reply::token("token")
+            } else if hit > ok_after {
+                Reply::new(200, COB)
+            } else {
+                Reply::new(status, "{}")
+            }
+        })
+        .await
+    }
+
+    /// Policy with fixed, tiny delays that records `(attempt, retry_in)`.
+    fn recorded(policy: RetryPolicy) -> (RetryPolicy, Attempts) {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let policy = policy.jitter(false).on_attempt({
+            let seen = Arc::clone(&seen);
+            move |attempt| {
+                assert!(matches!(attempt.outcome, AttemptOutcome::Status(_)));
+                seen.lock()
+                    .unwrap()
+                    .push((attempt.attempt, attempt.retry_in));
+            }
+        });
+        (policy, seen)
+    }
+
+    fn ms(value: u64) -> Option<Duration> {
+        Some(Duration::from_millis(value))
+    }
+
+    #[tokio::test]
+    async fn retries_until_success() {
+        let server = server(503, 2).await;
+        let (policy, seen) = recorded(RetryPolicy::new().initial_backoff(Duration::from_millis(1)));
+        let client = server.client(policy);
+        let txid = Txid::from_seed("cob");
+
+        client.cob_get(txid.as_str()).await.unwrap();
+        let path = format!("/v2/cob/{txid}");
+        assert_eq!(server.hits(&path), 3);
+        assert_eq!(*seen.lock().unwrap(), [(1, ms(1)), (2, ms(2)), (3, None)]);
+    }
+
+    #[tokio::test]
+    async fn stops_after_max_attempts() {
+        let server = server(503, usize::MAX).await;
+        let (policy, seen) = recorded(
+            RetryPolicy::new()
+                .max_attempts(5)
+                .initial_backoff(Duration::from_millis(1))
+                .max_backoff(Duration::from_millis(4)),
+        );
+        let client = server.client(policy);
+        let txid = Txid::from_seed("cob");
+
+        let error = client.cob_get(txid.as_str()).await.unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::SERVICE_UNAVAILABLE));
+        assert_eq!(server.hits(&format!("/v2/cob/{txid}")), 5);
+        assert_eq!(
+            *seen.lock().unwrap(),
+            [(1, ms(1)), (2, ms(2)), (3, ms(4)), (4, ms(4)), (5, None)]
+        );
+    }
+
+    #[tokio::test]
+    async fn retries_put_but_not_post() {
+        let server = server(503, usize::MAX).await;
+        let client = server.client(RetryPolicy::new().initial_backoff(Duration::from_millis(1)));
+        let payload = CobPayload::builder(
+            PixKey::email("loja@example.com").unwrap(),
+            Brl::from_cents(100),
+        )
+        .build()
+        .unwrap();
+        let txid = Txid::from_seed("cob");
+
+        client.cob_create(&payload).await.unwrap_err();
+        assert_eq!(server.hits("/v2/cob"), 1);
+        client
+            .cob_create_with_txid(&txid, &payload)
+            .await
+            .unwrap_err();
+        assert_eq!(server.hits(&format!("/v2/cob/{txid}")), 3);
+    }
+
+    #[tokio::test]
+    async fn waits_for_capped_retry_after() {
+        let server = TestServer::start(|_, path, hit| match (path, hit) {
+            ("/oauth/token", _) => Reply::token("token"),
+            (_, 1) => Reply::new(429, "{}").header("retry-after", "0"),
+            (_, 2) => Reply::new(429, "{}").header("retry-after", "3600"),
+            (_, 3) => Reply::new(429, "{}").header("retry-after", "Thu, 01 Jan 1970 00:00:00 GMT"),
+            _ => Reply::new(200, COB),
+        })
+        .await;
+        let (policy, seen) = recorded(
+            RetryPolicy::new()
+                .max_attempts(4)
+                .initial_backoff(Duration::from_millis(50))
+                .max_backoff(Duration::from_millis(80)),
+        );
+        let client = server.client(policy);
+
+        client
+            .cob_get(Txid::from_seed("cob").as_str())
+            .await
+            .unwrap();
+        assert_eq!(
+            *seen.lock().unwrap(),
+            [(1, ms(0)), (2, ms(80)), (3, ms(0)), (4, None)]
+        );
+    }
+
+    #[tokio::test]
+    async fn ignores_retry_after_when_disabled() {
+        let server = TestServer::start(|_, path, hit| match (path, hit) {
+            ("/oauth/token", _) => Reply::token("token"),
+            (_, 1) => Reply::new(429, "{}").header("retry-after", "0"),
+            _ => Reply::new(200, COB),
+        })
+        .await;
+        let (policy, seen) = recorded(
+            RetryPolicy::new()
+                .initial_backoff(Duration::from_millis(5))
+                .respect_retry_after(false),
+        );
+        let client = server.client(policy);
+
+        client
+            .cob_get(Txid::from_seed("cob").as_str())
+            .await
+            .unwrap();
+        assert_eq!(*seen.lock().unwrap(), [(1, ms(5)), (2, None)]);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_client_errors() {
+        let server = server(404, usize::MAX).await;
+        let (policy, seen) = recorded(RetryPolicy::new());
+        let client = server.client(policy);
+
+        client
+            .cob_get(Txid::from_seed("cob").as_str())
+            .await
+            .unwrap_err();
+        assert_eq!(*seen.lock().unwrap(), [(1, None)]);
+    }
+
+    #[test]
+    fn parses_imf_fixdate() {
+        assert_eq!(
+            parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"),
+            Some(784_111_777)
+        );
+        assert_eq!(parse_http_date("Thu, 01 Jan 1970 00:00:00 GMT"), Some(0));
+        assert_eq!(
+            parse_http_date("Thu, 29 Feb 2024 12:00:00 GMT"),
+            Some(1_709_208_000)
+        );
+    }
+
+    #[test]
+    fn rejects_other_date_forms() {
+        assert_eq!(parse_http_date("Sunday, 06-Nov-94 08:49:37 GMT"), None);
+        assert_eq!(parse_http_date("Sun Nov  6 08:49:37 1994"), None);
+        assert_eq!(parse_http_date("Sun, 06 Nov 1994 08:49:37 UTC"), None);
+        assert_eq!(parse_http_date("Sun, 6 Nov 1994 08:49:37 GMT"), None);
+    }
+}
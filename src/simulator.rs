@@ -0,0 +1,550 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use reqwest::StatusCode;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+use crate::api::{BillingApi, CarnetApi, PixChargesApi, PixTransfersApi, SplitApi, WebhooksApi};
+use crate::environment::{Api, Endpoints};
+use crate::error::Error;
+use crate::fake::FakeEfi;
+use crate::types::{
+    CobListQuery, PixEnviadoListQuery, PixRecebidoListQuery, PixTransactionPayload,
+};
+
+const TOKEN_TTL_SECS: u64 = 3600;
+/// Largest request body the simulator reads; larger ones get `413`.
+const MAX_BODY_LEN: usize = 1024 * 1024;
+
+/// Local HTTP server that emulates the Efí Pix and Billing endpoints called by
+/// [`Client`](crate::Client), for offline integration tests.
+///
+/// Records live in a [`FakeEfi`], reachable through [`Simulator::fake`] to
+/// inspect them or to settle charges (`ATIVA` → `CONCLUIDA`). OAuth endpoints
+/// require HTTP basic auth with the configured credentials; every other
+/// endpoint requires a bearer token issued for its API.
+pub struct Simulator {
+    addr: SocketAddr,
+    state: Arc<SimulatorState>,
+    task: JoinHandle<()>,
+}
+
+struct SimulatorState {
+    fake: FakeEfi,
+    client_id: String,
+    client_secret: String,
+    tokens: Mutex<HashMap<String, Api>>,
+}
+
+struct Request {
+    method: String,
+    path: String,
+    query: String,
+    authorization: Option<String>,
+    body: Vec<u8>,
+}
+
+struct Response {
+    status: StatusCode,
+    body: String,
+}
+
+impl Simulator {
+    /// Starts the simulator on an ephemeral port of `127.0.0.1`.
+    pub async fn start(
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+    ) -> Result<Self, Error> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(SimulatorState {
+            fake: FakeEfi::new(),
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            tokens: Mutex::new(HashMap::new()),
+        });
+
+        let task = tokio::spawn(serve(listener, Arc::clone(&state)));
+
+        Ok(Self { addr, state, task })
+    }
+
+    #[must_use]
+    pub const fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    #[must_use]
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Endpoints that route both APIs to this simulator.
+    #[must_use]
+    pub fn endpoints(&self) -> Endpoints {
+        let base_url = self.base_url();
+        Endpoints::new(base_url.clone(), base_url)
+    }
+
+    #[must_use]
+    pub fn fake(&self) -> &FakeEfi {
+        &self.state.fake
+    }
+}
+
+impl Drop for Simulator {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn serve(listener: TcpListener, state: Arc<SimulatorState>) {
+    while let Ok((stream, _)) = listener.accept().await {
+        let state = Arc::clone(&state);
+        tokio::spawn(async move {
+            let _ = handle_connection(stream, &state).await;
+        });
+    }
+}
+
+async fn handle_connection(stream: TcpStream, state: &SimulatorState) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_owned();
+    let target = parts.next().unwrap_or_default();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let (path, query) = (path.to_owned(), query.to_owned());
+
+    let mut content_length = 0;
+    let mut authorization = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.parse().unwrap_or(0);
+            } else if name.eq_ignore_ascii_case("authorization") {
+                authorization = Some(value.to_owned());
+            }
+        }
+    }
+
+    let response = if content_length > MAX_BODY_LEN {
+        Response::problem(
+            api_for(&path),
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "corpo da requisição excede o limite",
+        )
+    } else {
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).await?;
+        state
+            .handle(Request {
+                method,
+                path,
+                query,
+                authorization,
+                body,
+            })
+            .await
+    };
+
+    let head = format!(
+        "HTTP/1.1 {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+        response.status,
+        response.body.len()
+    );
+    let mut stream = reader.into_inner();
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(response.body.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+impl Response {
+    fn json(status: StatusCode, body: &impl Serialize) -> Self {
+        Self {
+            status,
+            body: serde_json::to_string(body).unwrap_or_default(),
+        }
+    }
+
+    fn problem(api: Api, status: StatusCode, detail: &str) -> Self {
+        let body = match api {
+            Api::Pix => json!({
+                "type": format!("https://pix.bcb.gov.br/api/v2/error/{}", status.as_u16()),
+                "title": status.canonical_reason().unwrap_or("Erro"),
+                "status": status.as_u16(),
+                "detail": detail,
+            }),
+            Api::Billing => json!({
+                "code": status.as_u16(),
+                "error": status.canonical_reason().unwrap_or("error"),
+                "error_description": detail,
+            }),
+        };
+        Self::json(status, &body)
+    }
+
+    fn from_result<T: Serialize>(status: StatusCode, result: Result<T, Error>) -> Self {
+        match result {
+            Ok(value) => Self::json(status, &value),
+            Err(error) => Self {
+                status: error.status().unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                body: error.body().map(str::to_owned).unwrap_or_default(),
+            },
+        }
+    }
+}
+
+fn parse_body<T: DeserializeOwned>(api: Api, request: &Request) -> Result<T, Response> {
+    serde_json::from_slice(&request.body)
+        .map_err(|error| Response::problem(api, StatusCode::BAD_REQUEST, &error.to_string()))
+}
+
+fn parse_id<T: std::str::FromStr>(api: Api, value: &str) -> Result<T, Response> {
+    value
+        .parse()
+        .map_err(|_| Response::problem(api, StatusCode::NOT_FOUND, "identificador inválido"))
+}
+
+fn api_for(path: &str) -> Api {
+    if path.starts_with("/v1/") {
+        Api::Billing
+    } else {
+        Api::Pix
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct CarnetListQuery {
+    begin_date: String,
+    end_date: String,
+}
+
+impl SimulatorState {
+    async fn handle(&self, request: Request) -> Response {
+        let api = api_for(&request.path);
+
+        let result = match request.path.as_str() {
+            "/oauth/token" | "/v1/authorize" if request.method == "POST" => {
+                Ok(self.issue_token(api, &request))
+            }
+            _ => match self.authorize(api, &request) {
+                Ok(()) => match api {
+                    Api::Pix => self.handle_pix(&request).await,
+                    Api::Billing => self.handle_billing(&request).await,
+                },
+                Err(response) => Err(response),
+            },
+        };
+
+        result.unwrap_or_else(|response| response)
+    }
+
+    fn issue_token(&self, api: Api, request: &Request) -> Response {
+        let credentials = request
+            .authorization
+            .as_deref()
+            .and_then(|value| value.strip_prefix("Basic "))
+            .and_then(|encoded| STANDARD.decode(encoded).ok())
+            .and_then(|decoded| String::from_utf8(decoded).ok());
+
+        let expected = format!("{}:{}", self.client_id, self.client_secret);
+        if credentials.as_deref() != Some(expected.as_str()) {
+            return Response::problem(api, StatusCode::UNAUTHORIZED, "credenciais inválidas");
+        }
+
+        let token = format!("sim-{:032x}", fastrand::u128(..));
+        self.tokens
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .insert(token.clone(), api);
+
+        Response::json(
+            StatusCode::OK,
+            &json!({
+                "access_token": token,
+                "token_type": "Bearer",
+                "expires_in": TOKEN_TTL_SECS,
+            }),
+        )
+    }
+
+    fn authorize(&self, api: Api, request: &Request) -> Result<(), Response> {
+        let token = request
+            .authorization
+            .as_deref()
+            .and_then(|value| value.strip_prefix("Bearer "));
+
+        let tokens = self
+            .tokens
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        match token.and_then(|token| tokens.get(token)) {
+            Some(issued_for) if *issued_for == api => Ok(()),
+            _ => Err(Response::problem(
+                api,
+                StatusCode::UNAUTHORIZED,
+                "token de acesso inválido",
+            )),
+        }
+    }
+
+    async fn handle_pix(&self, request: &Request) -> Result<Response, Response> {
+        let fake = &self.fake;
+        let api = Api::Pix;
+        let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+
+        let response = match (request.method.as_str(), segments.as_slice()) {
+            ("POST", ["v2", "cob"]) => Response::from_result(
+                StatusCode::CREATED,
+                fake.cob_create(&parse_body(api, request)?).await,
+            ),
+            ("PUT", ["v2", "cob", txid]) => Response::from_result(
+                StatusCode::CREATED,
+                fake.cob_update(txid, &parse_body(api, request)?).await,
+            ),
+            ("PATCH", ["v2", "cob", txid]) => Response::from_result(
+                StatusCode::OK,
+                fake.cob_patch(txid, &parse_body(api, request)?).await,
+            ),
+            ("GET", ["v2", "cob", txid]) => {
+                Response::from_result(StatusCode::OK, fake.cob_get(txid).await)
+            }
+            ("GET", ["v2", "cob"]) => {
+                let query: CobListQuery =
+                    serde_urlencoded::from_str(&request.query).map_err(|error| {
+                        Response::problem(api, StatusCode::BAD_REQUEST, &error.to_string())
+                    })?;
+                Response::from_result(StatusCode::OK, fake.cob_list(&query).await)
+            }
+            ("POST", ["v2", "cobv"]) => Response::from_result(
+                StatusCode::CREATED,
+                fake.cobv_create(&parse_body(api, request)?).await,
+            ),
+            ("PUT", ["v2", "cobv", txid]) => Response::from_result(
+                StatusCode::CREATED,
+                fake.cobv_update(txid, &parse_body(api, request)?).await,
+            ),
+            ("PATCH", ["v2", "cobv", txid]) => Response::from_result(
+                StatusCode::OK,
+                fake.cobv_patch(txid, &parse_body(api, request)?).await,
+            ),
+            ("GET", ["v2", "cobv", txid]) => {
+                Response::from_result(StatusCode::OK, fake.cobv_get(txid).await)
+            }
+            ("GET", ["v2", "cobv"]) => {
+                let query: CobListQuery =
+                    serde_urlencoded::from_str(&request.query).map_err(|error| {
+                        Response::problem(api, StatusCode::BAD_REQUEST, &error.to_string())
+                    })?;
+                Response::from_result(StatusCode::OK, fake.cobv_list(&query).await)
+            }
+            ("PUT", ["v3", "gn", "pix", id_envio]) => {
+                let mut payload: PixTransactionPayload = parse_body(api, request)?;
+                (*id_envio).clone_into(&mut payload.idempotency_key);
+                Response::from_result(StatusCode::CREATED, fake.pix_send(&payload).await)
+            }
+            ("GET", ["v2", "gn", "pix", "enviados"]) => {
+                let query: PixEnviadoListQuery = serde_urlencoded::from_str(&request.query)
+                    .map_err(|error| {
+                        Response::problem(api, StatusCode::BAD_REQUEST, &error.to_string())
+                    })?;
+                Response::from_result(StatusCode::OK, fake.pix_sent_list(&query).await)
+            }
+            ("GET", ["v2", "gn", "pix", "enviados", "id-envio", id_envio]) => {
+                Response::from_result(
+                    StatusCode::OK,
+                    fake.pix_get_transaction_by_id_envio(id_envio).await,
+                )
+            }
+            ("GET", ["v2", "gn", "pix", "enviados", end_to_end_id]) => Response::from_result(
+                StatusCode::OK,
+                fake.pix_get_transaction(end_to_end_id).await,
+            ),
+            ("GET", ["v2", "pix"]) => {
+                let query: PixRecebidoListQuery = serde_urlencoded::from_str(&request.query)
+                    .map_err(|error| {
+                        Response::problem(api, StatusCode::BAD_REQUEST, &error.to_string())
+                    })?;
+                Response::from_result(StatusCode::OK, fake.pix_received_list(&query).await)
+            }
+            ("PUT", ["v2", "pix", end_to_end_id, "devolucao", refund_id]) => Response::from_result(
+                StatusCode::CREATED,
+                fake.pix_refund_create(end_to_end_id, refund_id, &parse_body(api, request)?)
+                    .await,
+            ),
+            ("GET", ["v2", "pix", end_to_end_id, "devolucao", refund_id]) => Response::from_result(
+                StatusCode::OK,
+                fake.pix_refund_get(end_to_end_id, refund_id).await,
+            ),
+            ("GET", ["v2", "pix", end_to_end_id]) => {
+                Response::from_result(StatusCode::OK, fake.pix_received_get(end_to_end_id).await)
+            }
+            ("POST", ["v2", "webhook"]) => Response::from_result(
+                StatusCode::CREATED,
+                fake.webhook_create(&parse_body(api, request)?).await,
+            ),
+            ("GET", ["v2", "webhook"]) => {
+                Response::from_result(StatusCode::OK, fake.webhook_list().await)
+            }
+            ("PUT", ["v2", "webhook", id]) => Response::from_result(
+                StatusCode::OK,
+                fake.webhook_update(id, &parse_body(api, request)?).await,
+            ),
+            ("GET", ["v2", "webhook", id]) => {
+                Response::from_result(StatusCode::OK, fake.webhook_get(id).await)
+            }
+            ("DELETE", ["v2", "webhook", id]) => Response::from_result(
+                StatusCode::OK,
+                fake.webhook_delete(id).await.map(|()| json!({})),
+            ),
+            ("POST", ["v2", "gn", "split", "config"]) => Response::from_result(
+                StatusCode::CREATED,
+                fake.split_create_config(&parse_body(api, request)?).await,
+            ),
+            ("PUT", ["v2", "gn", "split", "config", id]) => Response::from_result(
+                StatusCode::OK,
+                fake.split_update_config(id, &parse_body(api, request)?)
+                    .await,
+            ),
+            ("GET", ["v2", "gn", "split", "config", id]) => {
+                Response::from_result(StatusCode::OK, fake.split_get_config(id).await)
+            }
+            ("PUT", ["v2", "gn", "split", "cob", txid, "vinculo", id]) => {
+                Response::from_result(StatusCode::OK, fake.split_link_cob(txid, id).await)
+            }
+            ("GET", ["v2", "gn", "split", "cob", txid]) => {
+                Response::from_result(StatusCode::OK, fake.split_get_cob(txid).await)
+            }
+            ("DELETE", ["v2", "gn", "split", "cob", txid, "vinculo"]) => {
+                Response::from_result(StatusCode::OK, fake.split_unlink_cob(txid).await)
+            }
+            ("PUT", ["v2", "gn", "split", "cobv", txid, "vinculo", id]) => {
+                Response::from_result(StatusCode::OK, fake.split_link_cobv(txid, id).await)
+            }
+            ("GET", ["v2", "gn", "split", "cobv", txid]) => {
+                Response::from_result(StatusCode::OK, fake.split_get_cobv(txid).await)
+            }
+            ("DELETE", ["v2", "gn", "split", "cobv", txid, "vinculo"]) => {
+                Response::from_result(StatusCode::OK, fake.split_unlink_cobv(txid).await)
+            }
+            _ => Response::problem(api, StatusCode::NOT_FOUND, "rota não encontrada"),
+        };
+
+        Ok(response)
+    }
+
+    async fn handle_billing(&self, request: &Request) -> Result<Response, Response> {
+        let fake = &self.fake;
+        let api = Api::Billing;
+        let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+
+        let response = match (request.method.as_str(), segments.as_slice()) {
+            ("POST", ["v1", "charge"]) => Response::from_result(
+                StatusCode::OK,
+                fake.billing_charge_create(&parse_body(api, request)?).await,
+            ),
+            ("POST", ["v1", "charge", "one-step"]) => Response::from_result(
+                StatusCode::OK,
+                fake.billing_charge_one_step(&parse_body(api, request)?)
+                    .await,
+            ),
+            ("POST", ["v1", "charge", id, "pay"]) => Response::from_result(
+                StatusCode::OK,
+                fake.billing_charge_pay(parse_id(api, id)?, &parse_body(api, request)?)
+                    .await,
+            ),
+            ("GET", ["v1", "charge", id]) => Response::from_result(
+                StatusCode::OK,
+                fake.billing_charge_get(parse_id(api, id)?).await,
+            ),
+            ("GET", ["v1", "notification", token]) => {
+                Response::from_result(StatusCode::OK, fake.billing_notification_get(token).await)
+            }
+            ("GET", ["v1", "charges"]) => {
+                let query: CarnetListQuery =
+                    serde_urlencoded::from_str(&request.query).map_err(|error| {
+                        Response::problem(api, StatusCode::BAD_REQUEST, &error.to_string())
+                    })?;
+                Response::from_result(
+                    StatusCode::OK,
+                    fake.carnet_list(&query.begin_date, &query.end_date).await,
+                )
+            }
+            ("POST", ["v1", "carnet"]) => Response::from_result(
+                StatusCode::OK,
+                fake.carnet_create(&parse_body(api, request)?).await,
+            ),
+            ("GET", ["v1", "carnet", id]) => {
+                Response::from_result(StatusCode::OK, fake.carnet_get(parse_id(api, id)?).await)
+            }
+            ("PUT", ["v1", "carnet", id, "metadata"]) => Response::from_result(
+                StatusCode::OK,
+                fake.carnet_update_metadata(parse_id(api, id)?, &parse_body(api, request)?)
+                    .await,
+            ),
+            ("PUT", ["v1", "carnet", id, "parcel", parcel]) => Response::from_result(
+                StatusCode::OK,
+                fake.carnet_update_parcel(
+                    parse_id(api, id)?,
+                    parse_id(api, parcel)?,
+                    &parse_body(api, request)?,
+                )
+                .await,
+            ),
+            ("PUT", ["v1", "carnet", id, "parcels"]) => Response::from_result(
+                StatusCode::OK,
+                fake.carnet_update_parcels(parse_id(api, id)?, &parse_body(api, request)?)
+                    .await,
+            ),
+            ("PUT", ["v1", "carnet", id, "cancel"]) => {
+                Response::from_result(StatusCode::OK, fake.carnet_cancel(parse_id(api, id)?).await)
+            }
+            ("PUT", ["v1", "carnet", id, "parcel", parcel, "cancel"]) => Response::from_result(
+                StatusCode::OK,
+                fake.carnet_cancel_parcel(parse_id(api, id)?, parse_id(api, parcel)?)
+                    .await,
+            ),
+            ("POST", ["v1", "carnet", id, "resend"]) => {
+                Response::from_result(StatusCode::OK, fake.carnet_resend(parse_id(api, id)?).await)
+            }
+            ("POST", ["v1", "carnet", id, "parcel", parcel, "resend"]) => Response::from_result(
+                StatusCode::OK,
+                fake.carnet_resend_parcel(parse_id(api, id)?, parse_id(api, parcel)?)
+                    .await,
+            ),
+            ("POST", ["v1", "carnet", id, "history"]) => Response::from_result(
+                StatusCode::OK,
+                fake.carnet_add_history(parse_id(api, id)?, &parse_body(api, request)?)
+                    .await,
+            ),
+            ("PUT", ["v1", "carnet", id, "settle"]) => {
+                Response::from_result(StatusCode::OK, fake.carnet_settle(parse_id(api, id)?).await)
+            }
+            ("PUT", ["v1", "carnet", id, "parcel", parcel, "settle"]) => Response::from_result(
+                StatusCode::OK,
+                fake.carnet_settle_parcel(parse_id(api, id)?, parse_id(api, parcel)?)
+                    .await,
+            ),
+            _ => Response::problem(api, StatusCode::NOT_FOUND, "rota não encontrada"),
+        };
+
+        Ok(response)
+    }
+}
@@ -2,6 +2,7 @@ use reqwest::Method;
 
 use crate::client::Client;
 use crate::error::Error;
+use crate::txid::Txid;
 use crate::types::{SplitConfigResponse, SplitLinkResponse, SplitPayload};
 
 impl Client {
@@ -34,18 +35,21 @@ impl Client {
         txid: &str,
         split_config_id: &str,
     ) -> Result<SplitLinkResponse, Error> {
+        let txid = Txid::parse(txid)?;
         let path = format!("/v2/gn/split/cob/{txid}/vinculo/{split_config_id}");
         self.send_authenticated::<serde_json::Value, SplitLinkResponse>(Method::PUT, &path, None)
             .await
     }
 
     pub async fn split_get_cob(&self, txid: &str) -> Result<SplitConfigResponse, Error> {
+        let txid = Txid::parse(txid)?;
         let path = format!("/v2/gn/split/cob/{txid}");
         self.send_authenticated::<serde_json::Value, SplitConfigResponse>(Method::GET, &path, None)
             .await
     }
 
     pub async fn split_unlink_cob(&self, txid: &str) -> Result<SplitLinkResponse, Error> {
+        let txid = Txid::parse(txid)?;
         let path = format!("/v2/gn/split/cob/{txid}/vinculo");
         self.send_authenticated::<serde_json::Value, SplitLinkResponse>(Method::DELETE, &path, None)
             .await
@@ -56,18 +60,21 @@ impl Client {
         txid: &str,
         split_config_id: &str,
     ) -> Result<SplitLinkResponse, Error> {
+        let txid = Txid::parse(txid)?;
         let path = format!("/v2/gn/split/cobv/{txid}/vinculo/{split_config_id}");
         self.send_authenticated::<serde_json::Value, SplitLinkResponse>(Method::PUT, &path, None)
             .await
     }
 
     pub async fn split_get_cobv(&self, txid: &str) -> Result<SplitConfigResponse, Error> {
+        let txid = Txid::parse(txid)?;
         let path = format!("/v2/gn/split/cobv/{txid}");
         self.send_authenticated::<serde_json::Value, SplitConfigResponse>(Method::GET, &path, None)
             .await
     }
 
     pub async fn split_unlink_cobv(&self, txid: &str) -> Result<SplitLinkResponse, Error> {
+        let txid = Txid::parse(txid)?;
         let path = format!("/v2/gn/split/cobv/{txid}/vinculo");
         self.send_authenticated::<serde_json::Value, SplitLinkResponse>(Method::DELETE, &path, None)
             .await
@@ -0,0 +1,203 @@
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Declares a status enum that (de)serializes from its wire string and keeps
+/// values it does not know about in `Unknown`, so new statuses added by Efí do
+/// not break deserialization.
+macro_rules! status_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident => $wire:literal,)+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)+
+            Unknown(String),
+        }
+
+        impl $name {
+            #[must_use]
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $wire,)+
+                    Self::Unknown(value) => value,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($wire => Self::$variant,)+
+                    other => Self::Unknown(other.to_owned()),
+                }
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Infallible;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                Ok(Self::from(value))
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                Ok(Self::from(value.as_str()))
+            }
+        }
+    };
+}
+
+status_enum! {
+    /// Status of an immediate (COB) or due-date (COBV) charge.
+    pub enum CobStatus {
+        Ativa => "ATIVA",
+        Concluida => "CONCLUIDA",
+        RemovidaPeloUsuarioRecebedor => "REMOVIDA_PELO_USUARIO_RECEBEDOR",
+        RemovidaPeloPsp => "REMOVIDA_PELO_PSP",
+    }
+}
+
+impl CobStatus {
+    /// Whether the charge can no longer be paid or changed.
+    #[must_use]
+    pub const fn is_final(&self) -> bool {
+        matches!(
+            self,
+            Self::Concluida | Self::RemovidaPeloUsuarioRecebedor | Self::RemovidaPeloPsp
+        )
+    }
+
+    #[must_use]
+    pub const fn is_paid(&self) -> bool {
+        matches!(self, Self::Concluida)
+    }
+}
+
+status_enum! {
+    /// Status of an outgoing Pix transfer.
+    pub enum PixTransferStatus {
+        EmProcessamento => "EM_PROCESSAMENTO",
+        Realizado => "REALIZADO",
+        NaoRealizado => "NAO_REALIZADO",
+    }
+}
+
+impl PixTransferStatus {
+    #[must_use]
+    pub const fn is_final(&self) -> bool {
+        matches!(self, Self::Realizado | Self::NaoRealizado)
+    }
+
+    #[must_use]
+    pub const fn is_paid(&self) -> bool {
+        matches!(self, Self::Realizado)
+    }
+}
+
+status_enum! {
+    /// Status of a Billing API charge, including each parcel of a carnet.
+    pub enum BillingChargeStatus {
+        New => "new",
+        Waiting => "waiting",
+        Identified => "identified",
+        Approved => "approved",
+        Paid => "paid",
+        Unpaid => "unpaid",
+        Refunded => "refunded",
+        Contested => "contested",
+        Canceled => "canceled",
+        Settled => "settled",
+        Link => "link",
+        Expired => "expired",
+    }
+}
+
+impl BillingChargeStatus {
+    #[must_use]
+    pub const fn is_final(&self) -> bool {
+        matches!(
+            self,
+            Self::Paid
+                | Self::Unpaid
+                | Self::Refunded
+                | Self::Canceled
+                | Self::Settled
+                | Self::Expired
+        )
+    }
+
+    /// Whether the charge was paid, either through Efí or settled manually.
+    #[must_use]
+    pub const fn is_paid(&self) -> bool {
+        matches!(self, Self::Paid | Self::Settled)
+    }
+}
+
+status_enum! {
+    /// Status of a carnet as a whole.
+    pub enum CarnetStatus {
+        Active => "active",
+        UpToDate => "up_to_date",
+        Unpaid => "unpaid",
+        Canceled => "canceled",
+        Finished => "finished",
+        Settled => "settled",
+    }
+}
+
+impl CarnetStatus {
+    #[must_use]
+    pub const fn is_final(&self) -> bool {
+        matches!(self, Self::Canceled | Self::Finished | Self::Settled)
+    }
+
+    /// Whether every parcel of the carnet was paid.
+    #[must_use]
+    pub const fn is_paid(&self) -> bool {
+        matches!(self, Self::Finished | Self::Settled)
+    }
+}
+
+status_enum! {
+    /// Status of a refund (devolução) of a received Pix.
+    pub enum DevolucaoStatus {
+        EmProcessamento => "EM_PROCESSAMENTO",
+        Devolvido => "DEVOLVIDO",
+        NaoRealizado => "NAO_REALIZADO",
+    }
+}
+
+impl DevolucaoStatus {
+    #[must_use]
+    pub const fn is_final(&self) -> bool {
+        matches!(self, Self::Devolvido | Self::NaoRealizado)
+    }
+
+    /// Whether the money was sent back to the payer.
+    #[must_use]
+    pub const fn is_returned(&self) -> bool {
+        matches!(self, Self::Devolvido)
+    }
+}
@@ -0,0 +1,165 @@
+//! Scripted HTTP server for unit tests that need to see what `Client` sends.
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+use crate::client::{Client, ClientBuilder};
+use crate::environment::Endpoints;
+use crate::retry::RetryPolicy;
+
+type Responder = dyn Fn(&str, &str, usize) -> Reply + Send + Sync;
+
+pub(crate) struct Reply {
+    status: u16,
+    headers: Vec<(&'static str, String)>,
+    body: String,
+    delay: Duration,
+}
+
+impl Reply {
+    pub(crate) fn new(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+            delay: Duration::ZERO,
+        }
+    }
+
+    pub(crate) fn token(value: &str) -> Self {
+        Self::new(
+            200,
+            format!(r#"{{"access_token":"{value}","expires_in":3600}}"#),
+        )
+    }
+
+    pub(crate) fn header(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+
+    pub(crate) const fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+/// Answers each request with `respond(method, path, hit)`, where `hit` is the
+/// 1-based count of requests seen so far for that path.
+pub(crate) struct TestServer {
+    addr: SocketAddr,
+    requests: Arc<Mutex<Vec<(String, String)>>>,
+    task: JoinHandle<()>,
+}
+
+impl TestServer {
+    pub(crate) async fn start(
+        respond: impl Fn(&str, &str, usize) -> Reply + Send + Sync + 'static,
+    ) -> Self {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let respond: Arc<Responder> = Arc::new(respond);
+
+        let task = tokio::spawn({
+            let requests = Arc::clone(&requests);
+            async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    tokio::spawn(handle(stream, Arc::clone(&requests), Arc::clone(&respond)));
+                }
+            }
+        });
+
+        Self {
+            addr,
+            requests,
+            task,
+        }
+    }
+
+    /// A client for this server that retries with the given policy.
+    pub(crate) fn client(&self, retry_policy: RetryPolicy) -> Client {
+        let base_url = format!("http://{}", self.addr);
+        ClientBuilder::new()
+            .credentials("id", "secret")
+            .endpoints(Endpoints::new(base_url.clone(), base_url))
+            .retry_policy(retry_policy)
+            .build()
+            .unwrap()
+    }
+
+    pub(crate) fn hits(&self, path: &str) -> usize {
+        self.requests()
+            .iter()
+            .filter(|(_, seen)| seen == path)
+            .count()
+    }
+
+    /// `(method, path)` of every request, in arrival order.
+    pub(crate) fn requests(&self) -> Vec<(String, String)> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn handle(
+    stream: TcpStream,
+    requests: Arc<Mutex<Vec<(String, String)>>>,
+    respond: Arc<Responder>,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_owned();
+    let path = parts.next().unwrap_or_default().to_owned();
+
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse().unwrap_or(0);
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await?;
+
+    let hit = {
+        let mut requests = requests.lock().unwrap();
+        requests.push((method.clone(), path.clone()));
+        requests.iter().filter(|(_, seen)| *seen == path).count()
+    };
+    let reply = respond(&method, &path, hit);
+    tokio::time::sleep(reply.delay).await;
+
+    let mut head = format!(
+        "HTTP/1.1 {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n",
+        reply.status,
+        reply.body.len()
+    );
+    for (name, value) in &reply.headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str("\r\n");
+
+    let mut stream = reader.into_inner();
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(reply.body.as_bytes()).await?;
+    stream.shutdown().await
+}
@@ -0,0 +1,109 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::Error;
+
+/// Charge identifier: 26 to 35 ASCII letters or digits.
+///
+/// Use [`Txid::random`] or [`Txid::from_seed`] to pick the txid yourself and
+/// create the charge with `cob_create_with_txid`; retrying the same request then
+/// cannot create a second charge.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Txid(String);
+
+const GENERATED_LEN: usize = 32;
+
+impl Txid {
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        if !(26..=35).contains(&value.len()) {
+            return Err(Error::InvalidValue {
+                kind: "txid",
+                reason: "must have 26 to 35 characters",
+            });
+        }
+        if !value.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return Err(Error::InvalidValue {
+                kind: "txid",
+                reason: "must contain only letters and digits",
+            });
+        }
+        Ok(Self(value.to_owned()))
+    }
+
+    /// 32 random letters and digits.
+    #[must_use]
+    pub fn random() -> Self {
+        Self(random_id())
+    }
+
+    /// 32 hexadecimal digits derived from `seed`, such as an order id. The
+    /// same seed always gives the same txid, across runs, platforms and
+    /// releases of this crate.
+    #[must_use]
+    pub fn from_seed(seed: impl AsRef<[u8]>) -> Self {
+        Self(seeded_id(seed.as_ref()))
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// 32 random letters and digits, also used for `idEnvio`.
+pub(crate) fn random_id() -> String {
+    std::iter::repeat_with(fastrand::alphanumeric)
+        .take(GENERATED_LEN)
+        .collect()
+}
+
+/// 32 hexadecimal digits that depend only on `seed`, also used for `idEnvio`.
+pub(crate) fn seeded_id(seed: &[u8]) -> String {
+    let high = fnv1a(0, seed);
+    let low = fnv1a(1, seed);
+    format!("{high:016x}{low:016x}")
+}
+
+/// 64-bit FNV-1a over `salt` followed by `bytes`.
+fn fnv1a(salt: u8, bytes: &[u8]) -> u64 {
+    std::iter::once(&salt)
+        .chain(bytes)
+        .fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+impl AsRef<str> for Txid {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for Txid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for Txid {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+impl Serialize for Txid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Txid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
@@ -2,20 +2,30 @@ use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use serde_with::skip_serializing_none;
 
+use crate::datetime::{Date, Timestamp};
+use crate::document::{Cnpj, Cpf};
+use crate::money::Brl;
+use crate::pix_key::PixKey;
+use crate::status::{
+    BillingChargeStatus, CarnetStatus, CobStatus, DevolucaoStatus, PixTransferStatus,
+};
+use crate::txid::{random_id, seeded_id};
+
 // ========== Split de pagamento Pix ==========
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SplitRepasse {
     pub tipo: String,
-    pub valor: String,
+    /// Amount, or percentage when `tipo` is `porcentagem`; both use two decimal places.
+    pub valor: Brl,
     pub favorecido: SplitFavorecido,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SplitFavorecido {
-    pub cpf: String,
+    pub cpf: Cpf,
     pub conta: String,
 }
 
@@ -23,7 +33,8 @@ pub struct SplitFavorecido {
 #[serde(rename_all = "camelCase")]
 pub struct SplitParte {
     pub tipo: String,
-    pub valor: String,
+    /// Amount, or percentage when `tipo` is `porcentagem`; both use two decimal places.
+    pub valor: Brl,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -73,39 +84,90 @@ pub struct SplitLinkResponse {
 #[serde(rename_all = "camelCase")]
 pub struct CobPayload {
     pub calendario: CobCalendario,
-    pub devedor: Option<CobPessoa>,
+    pub devedor: Option<Devedor>,
     pub valor: CobValor,
-    pub chave: String,
+    pub chave: PixKey,
     pub solicitacao_pagador: Option<String>,
     pub info_adicionais: Option<Vec<CobInfoAdicional>>,
 }
 
 #[skip_serializing_none]
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CobCalendario {
-    pub data_de_vencimento: Option<String>,
-    pub validade_apos_vencimento: Option<i32>,
-}
-
+    /// Lifetime of the charge in seconds, counted from its creation. The API
+    /// defaults to 86400 (24 hours).
+    pub expiracao: Option<u32>,
+}
+
+/// Debtor of a charge: a person identified by CPF or a company by CNPJ.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Devedor {
+    Fisica { cpf: Cpf, nome: String },
+    Juridica { cnpj: Cnpj, nome: String },
+}
+
+impl Devedor {
+    #[must_use]
+    pub fn nome(&self) -> &str {
+        match self {
+            Self::Fisica { nome, .. } | Self::Juridica { nome, .. } => nome,
+        }
+    }
+
+    #[must_use]
+    pub const fn cpf(&self) -> Option<&Cpf> {
+        match self {
+            Self::Fisica { cpf, .. } => Some(cpf),
+            Self::Juridica { .. } => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn cnpj(&self) -> Option<&Cnpj> {
+        match self {
+            Self::Fisica { .. } => None,
+            Self::Juridica { cnpj, .. } => Some(cnpj),
+        }
+    }
+}
+
+/// Debtor as returned on a charge. Efí may mask part of the document, so it
+/// is kept as sent rather than parsed into [`Cpf`] or [`Cnpj`].
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct CobPessoa {
+pub struct DevedorResponse {
+    pub nome: Option<String>,
     pub cpf: Option<String>,
     pub cnpj: Option<String>,
-    pub nome: Option<String>,
+}
+
+impl From<Devedor> for DevedorResponse {
+    fn from(devedor: Devedor) -> Self {
+        match devedor {
+            Devedor::Fisica { cpf, nome } => Self {
+                nome: Some(nome),
+                cpf: Some(cpf.to_string()),
+                cnpj: None,
+            },
+            Devedor::Juridica { cnpj, nome } => Self {
+                nome: Some(nome),
+                cpf: None,
+                cnpj: Some(cnpj.to_string()),
+            },
+        }
+    }
 }
 
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CobValor {
-    pub original: String,
-    pub multa: Option<String>,
-    pub juros: Option<String>,
-    pub desconto: Option<String>,
-    pub abatimento: Option<String>,
+    pub original: Brl,
+    pub multa: Option<Brl>,
+    pub juros: Option<Brl>,
+    pub desconto: Option<Brl>,
+    pub abatimento: Option<Brl>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -121,23 +183,305 @@ pub struct CobInfoAdicional {
 pub struct CobResponse {
     pub txid: String,
     pub calendario: Option<CobCalendarioResponse>,
-    pub devedor: Option<CobPessoa>,
+    pub revisao: Option<i32>,
+    pub loc: Option<CobLoc>,
+    pub location: Option<String>,
+    pub status: Option<CobStatus>,
+    pub devedor: Option<DevedorResponse>,
     pub valor: Option<CobValor>,
     pub chave: Option<String>,
     pub solicitacao_pagador: Option<String>,
     pub info_adicionais: Option<Vec<CobInfoAdicional>>,
-    pub br: Option<String>,
-    pub status: Option<String>,
-    pub criacao_date_time: Option<String>,
+    pub pix_copia_e_cola: Option<String>,
+    /// Payments received for the charge, with their refunds.
+    pub pix: Option<Vec<PixRecebido>>,
 }
 
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CobCalendarioResponse {
-    pub criacao_date_time: Option<String>,
-    pub data_de_vencimento: Option<String>,
-    pub validade_apos_vencimento: Option<i32>,
+    pub criacao: Option<Timestamp>,
+    pub expiracao: Option<u32>,
+}
+
+impl CobResponse {
+    /// The payment that settled the charge, if any.
+    #[must_use]
+    pub fn settled_by(&self) -> Option<&PixRecebido> {
+        self.pix.as_deref()?.first()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl CobResponse {
+    /// See [`CobCalendarioResponse::expires_at`].
+    #[must_use]
+    pub fn expires_at(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        self.calendario.as_ref()?.expires_at()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl CobCalendarioResponse {
+    /// Moment the charge stops accepting payments: `criacao` plus `expiracao`
+    /// seconds. `None` if the response omitted either field.
+    #[must_use]
+    pub fn expires_at(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        let criacao = self.criacao.as_ref()?.to_datetime();
+        criacao.checked_add_signed(chrono::TimeDelta::seconds(i64::from(self.expiracao?)))
+    }
+}
+
+/// Payload location (`loc`) a charge is bound to.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CobLoc {
+    pub id: i64,
+    pub location: String,
+    pub tipo_cob: TipoCob,
+    pub criacao: Option<Timestamp>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TipoCob {
+    Cob,
+    Cobv,
+}
+
+/// Pix received for a charge or directly on a key.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PixRecebido {
+    pub end_to_end_id: String,
+    pub txid: Option<String>,
+    pub valor: Brl,
+    pub chave: Option<String>,
+    pub horario: Timestamp,
+    /// Free text the payer typed when paying.
+    pub info_pagador: Option<String>,
+    pub componentes_valor: Option<ComponentesValor>,
+    #[serde(default)]
+    pub devolucoes: Vec<Devolucao>,
+    /// Efí-specific details, such as who paid.
+    pub gn_extras: Option<PixGnExtras>,
+}
+
+/// How `valor` breaks down: the charge amount plus fines and interest, minus
+/// discounts, and any Pix Saque/Troco cash-out.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentesValor {
+    pub original: Option<ComponenteValor>,
+    pub saque: Option<ComponenteValor>,
+    pub troco: Option<ComponenteValor>,
+    pub juros: Option<ComponenteValor>,
+    pub multa: Option<ComponenteValor>,
+    pub abatimento: Option<ComponenteValor>,
+    pub desconto: Option<ComponenteValor>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponenteValor {
+    pub valor: Brl,
+    /// Only for `saque` and `troco`.
+    pub modalidade_agente: Option<String>,
+    /// ISPB of the cash-out provider, only for `saque` and `troco`.
+    pub prestador_de_servico_de_saque: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PixGnExtras {
+    pub pagador: Option<PixPagador>,
+}
+
+/// Payer of a received Pix. Efí may mask part of the document, so it is kept
+/// as sent rather than parsed into [`Cpf`] or [`Cnpj`].
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PixPagador {
+    pub nome: Option<String>,
+    pub cpf: Option<String>,
+    pub cnpj: Option<String>,
+    /// ISPB of the payer's institution.
+    pub codigo_banco: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Devolucao {
+    /// Identifier chosen by the receiver when requesting the refund.
+    pub id: String,
+    pub rtr_id: Option<String>,
+    pub valor: Brl,
+    pub natureza: Option<NaturezaDevolucao>,
+    pub descricao: Option<String>,
+    pub horario: DevolucaoHorario,
+    pub status: DevolucaoStatus,
+    pub motivo: Option<String>,
+}
+
+/// Body of `pix_refund_create`.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DevolucaoPayload {
+    pub valor: Brl,
+    /// Defaults to [`NaturezaDevolucao::Original`] on the API side.
+    pub natureza: Option<NaturezaDevolucao>,
+    pub descricao: Option<String>,
+}
+
+impl DevolucaoPayload {
+    #[must_use]
+    pub const fn new(valor: Brl) -> Self {
+        Self {
+            valor,
+            natureza: None,
+            descricao: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn natureza(mut self, value: NaturezaDevolucao) -> Self {
+        self.natureza = Some(value);
+        self
+    }
+
+    #[must_use]
+    pub fn descricao(mut self, value: impl Into<String>) -> Self {
+        self.descricao = Some(value.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NaturezaDevolucao {
+    /// Refund of the Pix itself.
+    Original,
+    /// Refund of the cash handed out by a Pix Saque or Pix Troco.
+    Retirada,
+    /// Special refund (MED) for an operational failure.
+    MedOperacional,
+    /// Special refund (MED) for suspected fraud.
+    MedFraude,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DevolucaoHorario {
+    pub solicitacao: Option<Timestamp>,
+    pub liquidacao: Option<Timestamp>,
+}
+
+/// Filters for `cob_list` and `cobv_list`. `inicio` and `fim` bound the
+/// creation time of the charges and are required by the API.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CobListQuery {
+    pub inicio: Timestamp,
+    pub fim: Timestamp,
+    pub cpf: Option<Cpf>,
+    pub cnpj: Option<Cnpj>,
+    pub location_presente: Option<bool>,
+    pub status: Option<CobStatus>,
+    /// Zero-based page index.
+    #[serde(rename = "paginacao.paginaAtual")]
+    pub pagina_atual: Option<u32>,
+    #[serde(rename = "paginacao.itensPorPagina")]
+    pub itens_por_pagina: Option<u32>,
+}
+
+impl CobListQuery {
+    #[must_use]
+    pub const fn new(inicio: Timestamp, fim: Timestamp) -> Self {
+        Self {
+            inicio,
+            fim,
+            cpf: None,
+            cnpj: None,
+            location_presente: None,
+            status: None,
+            pagina_atual: None,
+            itens_por_pagina: None,
+        }
+    }
+
+    #[must_use]
+    pub fn cpf(mut self, value: Cpf) -> Self {
+        self.cpf = Some(value);
+        self
+    }
+
+    #[must_use]
+    pub fn cnpj(mut self, value: Cnpj) -> Self {
+        self.cnpj = Some(value);
+        self
+    }
+
+    /// Only charges with (`true`) or without (`false`) a location.
+    #[must_use]
+    pub const fn location_presente(mut self, value: bool) -> Self {
+        self.location_presente = Some(value);
+        self
+    }
+
+    #[must_use]
+    pub fn status(mut self, value: CobStatus) -> Self {
+        self.status = Some(value);
+        self
+    }
+
+    #[must_use]
+    pub const fn pagina_atual(mut self, value: u32) -> Self {
+        self.pagina_atual = Some(value);
+        self
+    }
+
+    #[must_use]
+    pub const fn itens_por_pagina(mut self, value: u32) -> Self {
+        self.itens_por_pagina = Some(value);
+        self
+    }
+}
+
+/// Query echoed back by list endpoints, with the page that was returned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListParametros {
+    pub inicio: Timestamp,
+    pub fim: Timestamp,
+    pub paginacao: Paginacao,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Paginacao {
+    pub pagina_atual: u32,
+    pub itens_por_pagina: u32,
+    pub quantidade_de_paginas: u32,
+    pub quantidade_total_de_itens: u32,
+}
+
+impl Paginacao {
+    #[must_use]
+    pub const fn has_next_page(&self) -> bool {
+        self.pagina_atual.saturating_add(1) < self.quantidade_de_paginas
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CobListResponse {
+    pub parametros: ListParametros,
+    pub cobs: Vec<CobResponse>,
 }
 
 // ========== COBV - Expiring Charge (Cobrança com Vencimento) ==========
@@ -147,17 +491,69 @@ pub struct CobCalendarioResponse {
 #[serde(rename_all = "camelCase")]
 pub struct CobvPayload {
     pub calendario: CobvCalendario,
-    pub devedor: Option<CobPessoa>,
+    pub devedor: Option<CobvDevedor>,
     pub valor: CobValor,
-    pub chave: String,
+    pub chave: PixKey,
     pub solicitacao_pagador: Option<String>,
     pub info_adicionais: Option<Vec<CobInfoAdicional>>,
 }
 
+/// COBV debtor, which may also carry an address and email.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CobvDevedor {
+    #[serde(flatten)]
+    pub pessoa: Devedor,
+    pub email: Option<String>,
+    pub logradouro: Option<String>,
+    pub cidade: Option<String>,
+    pub uf: Option<String>,
+    pub cep: Option<String>,
+}
+
+impl From<Devedor> for CobvDevedor {
+    fn from(pessoa: Devedor) -> Self {
+        Self {
+            pessoa,
+            email: None,
+            logradouro: None,
+            cidade: None,
+            uf: None,
+            cep: None,
+        }
+    }
+}
+
+/// [`CobvDevedor`] as returned on a charge, with the document kept as sent.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CobvDevedorResponse {
+    #[serde(flatten)]
+    pub pessoa: DevedorResponse,
+    pub email: Option<String>,
+    pub logradouro: Option<String>,
+    pub cidade: Option<String>,
+    pub uf: Option<String>,
+    pub cep: Option<String>,
+}
+
+impl From<CobvDevedor> for CobvDevedorResponse {
+    fn from(devedor: CobvDevedor) -> Self {
+        Self {
+            pessoa: devedor.pessoa.into(),
+            email: devedor.email,
+            logradouro: devedor.logradouro,
+            cidade: devedor.cidade,
+            uf: devedor.uf,
+            cep: devedor.cep,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CobvCalendario {
-    pub data_de_vencimento: String,
+    pub data_de_vencimento: Date,
     pub validade_apos_vencimento: i32,
 }
 
@@ -167,31 +563,41 @@ pub struct CobvCalendario {
 pub struct CobvResponse {
     pub txid: String,
     pub calendario: Option<CobvCalendarioResponse>,
-    pub devedor: Option<CobPessoa>,
+    pub revisao: Option<i32>,
+    pub loc: Option<CobLoc>,
+    pub location: Option<String>,
+    pub status: Option<CobStatus>,
+    pub devedor: Option<CobvDevedorResponse>,
     pub valor: Option<CobValor>,
     pub chave: Option<String>,
     pub solicitacao_pagador: Option<String>,
     pub info_adicionais: Option<Vec<CobInfoAdicional>>,
-    pub br: Option<String>,
-    pub status: Option<String>,
-    pub criacao_date_time: Option<String>,
+    pub pix_copia_e_cola: Option<String>,
+    /// Payments received for the charge, with their refunds.
+    pub pix: Option<Vec<PixRecebido>>,
 }
 
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CobvCalendarioResponse {
-    pub criacao_date_time: Option<String>,
-    pub data_de_vencimento: Option<String>,
+    pub criacao: Option<Timestamp>,
+    pub data_de_vencimento: Option<Date>,
     pub validade_apos_vencimento: Option<i32>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CobvListResponse {
+    pub parametros: ListParametros,
+    pub cobs: Vec<CobvResponse>,
+}
+
 // ========== Webhook Management ==========
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebhookPayload {
     pub url: String,
-    pub chave: String,
+    pub chave: PixKey,
 }
 
 #[skip_serializing_none]
@@ -201,7 +607,7 @@ pub struct WebhookResponse {
     pub id: String,
     pub url: String,
     pub chave: Option<String>,
-    pub criacao_date_time: Option<String>,
+    pub criacao_date_time: Option<Timestamp>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -211,40 +617,364 @@ pub struct WebhooksListResponse {
 
 // ========== PIX Transactions ==========
 
+/// Body of `pix_send` (`PUT /v3/gn/pix/{idEnvio}`).
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PixTransactionPayload {
-    pub valor: String,
-    pub chave_destino: String,
-    pub descricao: Option<String>,
-    pub idempotency_key: Option<String>,
+    pub valor: Brl,
+    pub pagador: PixEnvioPagador,
+    pub favorecido: PixFavorecido,
+    /// Source of the `idEnvio` path segment; see [`PixTransactionPayload::id_envio`].
+    /// Random unless set with [`PixTransactionPayload::idempotency_key`].
+    #[serde(skip)]
+    pub idempotency_key: String,
+}
+
+impl PixTransactionPayload {
+    /// Sends `valor` from one of the account's keys (`pagador`) to `favorecido`,
+    /// under a random idempotency key until one is set.
+    #[must_use]
+    pub fn new(valor: Brl, chave_pagador: PixKey, favorecido: PixFavorecido) -> Self {
+        Self {
+            valor,
+            pagador: PixEnvioPagador {
+                chave: chave_pagador,
+                info_pagador: None,
+            },
+            favorecido,
+            idempotency_key: random_id(),
+        }
+    }
+
+    /// Message shown to the receiver.
+    #[must_use]
+    pub fn info_pagador(mut self, value: impl Into<String>) -> Self {
+        self.pagador.info_pagador = Some(value.into());
+        self
+    }
+
+    #[must_use]
+    pub fn idempotency_key(mut self, value: impl Into<String>) -> Self {
+        self.idempotency_key = value.into();
+        self
+    }
+
+    /// `idEnvio` derived from `idempotency_key`: the key itself when it is 1 to
+    /// 35 letters or digits, otherwise 32 hexadecimal digits hashed from it.
+    /// Sending again with the same key reaches the same `idEnvio`, which Efí
+    /// never pays twice, so keep it to look the transfer up if `pix_send`
+    /// fails without an answer.
+    #[must_use]
+    pub fn id_envio(&self) -> String {
+        let key = &self.idempotency_key;
+        if (1..=35).contains(&key.len()) && key.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            key.clone()
+        } else {
+            seeded_id(key.as_bytes())
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PixEnvioPagador {
+    /// Key of the sending account, registered with Efí.
+    pub chave: PixKey,
+    pub info_pagador: Option<String>,
+}
+
+/// Receiver of a Pix: a key, optionally checked against the owner's
+/// document, or a bank account typed in manually.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PixFavorecido {
+    Chave {
+        chave: PixKey,
+        /// The transfer fails unless the key belongs to this CPF.
+        cpf: Option<Cpf>,
+        /// The transfer fails unless the key belongs to this CNPJ.
+        cnpj: Option<Cnpj>,
+    },
+    #[serde(rename_all = "camelCase")]
+    ContaBanco { conta_banco: ContaBanco },
+}
+
+impl PixFavorecido {
+    #[must_use]
+    pub const fn chave(chave: PixKey) -> Self {
+        Self::Chave {
+            chave,
+            cpf: None,
+            cnpj: None,
+        }
+    }
+}
+
+impl From<PixKey> for PixFavorecido {
+    fn from(chave: PixKey) -> Self {
+        Self::chave(chave)
+    }
+}
+
+impl From<ContaBanco> for PixFavorecido {
+    fn from(conta_banco: ContaBanco) -> Self {
+        Self::ContaBanco { conta_banco }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContaBanco {
+    /// Account holder's name and CPF or CNPJ.
+    #[serde(flatten)]
+    pub titular: Devedor,
+    /// ISPB of the receiving institution (8 digits).
+    pub codigo_banco: String,
+    /// Branch, without the check digit.
+    pub agencia: String,
+    /// Account number with the check digit, without punctuation.
+    pub conta: String,
+    pub tipo_conta: TipoConta,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TipoConta {
+    /// Checking account (conta corrente).
+    Cacc,
+    /// Savings account (poupança).
+    Svgs,
+    /// Salary account (conta salário).
+    Slrp,
+    /// Payment account (conta de pagamento).
+    Tran,
 }
 
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PixTransactionResponse {
-    pub id: String,
+    pub id_envio: String,
+    #[serde(rename = "e2eId")]
     pub end_to_end_id: String,
-    pub valor: String,
-    pub status: String,
-    pub chave_destino: Option<String>,
-    pub data_hora: Option<String>,
+    pub valor: Brl,
+    pub horario: PixEnvioHorario,
+    pub status: PixTransferStatus,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PixEnvioHorario {
+    pub solicitacao: Option<Timestamp>,
+    pub liquidacao: Option<Timestamp>,
 }
 
+/// Sent Pix, as returned by `pix_get_transaction`,
+/// `pix_get_transaction_by_id_envio` and `pix_sent_list`.
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PixTransactionDetailResponse {
-    pub id: String,
     pub end_to_end_id: String,
-    pub valor: String,
-    pub status: String,
-    pub chave_destino: Option<String>,
-    pub chave_origem: Option<String>,
-    pub data_hora: Option<String>,
-    pub motivo_cancelamento: Option<String>,
+    pub id_envio: Option<String>,
+    pub valor: Brl,
+    pub horario: PixEnvioHorario,
+    pub status: PixTransferStatus,
+    pub pagador: Option<PixEnviadoPagador>,
+    pub favorecido: Option<PixEnviadoFavorecido>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PixEnviadoPagador {
+    pub chave: Option<String>,
+    pub info_pagador: Option<String>,
+}
+
+/// Receiver of a sent Pix: the key and its owner, or the bank account typed in.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PixEnviadoFavorecido {
+    pub chave: Option<String>,
+    pub identificacao: Option<PixIdentificacao>,
+    pub conta_banco: Option<PixEnviadoContaBanco>,
+}
+
+/// Name and document of an account holder. Efí may mask part of the
+/// document, so it is kept as sent.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PixIdentificacao {
+    pub nome: Option<String>,
+    pub cpf: Option<String>,
+    pub cnpj: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PixEnviadoContaBanco {
+    #[serde(flatten)]
+    pub titular: PixIdentificacao,
+    pub codigo_banco: Option<String>,
+    pub agencia: Option<String>,
+    pub conta: Option<String>,
+    pub tipo_conta: Option<TipoConta>,
+}
+
+/// Filters for `pix_sent_list`. `inicio` and `fim` bound the request time and
+/// are required by the API.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PixEnviadoListQuery {
+    pub inicio: Timestamp,
+    pub fim: Timestamp,
+    pub status: Option<PixTransferStatus>,
+    pub devolucao_presente: Option<bool>,
+    /// Zero-based page index.
+    #[serde(rename = "paginacao.paginaAtual")]
+    pub pagina_atual: Option<u32>,
+    #[serde(rename = "paginacao.itensPorPagina")]
+    pub itens_por_pagina: Option<u32>,
+}
+
+impl PixEnviadoListQuery {
+    #[must_use]
+    pub const fn new(inicio: Timestamp, fim: Timestamp) -> Self {
+        Self {
+            inicio,
+            fim,
+            status: None,
+            devolucao_presente: None,
+            pagina_atual: None,
+            itens_por_pagina: None,
+        }
+    }
+
+    #[must_use]
+    pub fn status(mut self, value: PixTransferStatus) -> Self {
+        self.status = Some(value);
+        self
+    }
+
+    #[must_use]
+    pub const fn devolucao_presente(mut self, value: bool) -> Self {
+        self.devolucao_presente = Some(value);
+        self
+    }
+
+    #[must_use]
+    pub const fn pagina_atual(mut self, value: u32) -> Self {
+        self.pagina_atual = Some(value);
+        self
+    }
+
+    #[must_use]
+    pub const fn itens_por_pagina(mut self, value: u32) -> Self {
+        self.itens_por_pagina = Some(value);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PixEnviadoListResponse {
+    pub parametros: ListParametros,
+    pub pix: Vec<PixTransactionDetailResponse>,
+}
+
+/// Filters for `pix_received_list`. `inicio` and `fim` bound `horario` and
+/// are required by the API.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PixRecebidoListQuery {
+    pub inicio: Timestamp,
+    pub fim: Timestamp,
+    pub txid: Option<String>,
+    /// Only Pix that paid a charge (`true`) or were sent straight to a key (`false`).
+    #[serde(rename = "txIdPresente")]
+    pub txid_presente: Option<bool>,
+    pub devolucao_presente: Option<bool>,
+    pub cpf: Option<Cpf>,
+    pub cnpj: Option<Cnpj>,
+    /// Zero-based page index.
+    #[serde(rename = "paginacao.paginaAtual")]
+    pub pagina_atual: Option<u32>,
+    #[serde(rename = "paginacao.itensPorPagina")]
+    pub itens_por_pagina: Option<u32>,
+}
+
+impl PixRecebidoListQuery {
+    #[must_use]
+    pub const fn new(inicio: Timestamp, fim: Timestamp) -> Self {
+        Self {
+            inicio,
+            fim,
+            txid: None,
+            txid_presente: None,
+            devolucao_presente: None,
+            cpf: None,
+            cnpj: None,
+            pagina_atual: None,
+            itens_por_pagina: None,
+        }
+    }
+
+    #[must_use]
+    pub fn txid(mut self, value: impl Into<String>) -> Self {
+        self.txid = Some(value.into());
+        self
+    }
+
+    #[must_use]
+    pub const fn txid_presente(mut self, value: bool) -> Self {
+        self.txid_presente = Some(value);
+        self
+    }
+
+    #[must_use]
+    pub const fn devolucao_presente(mut self, value: bool) -> Self {
+        self.devolucao_presente = Some(value);
+        self
+    }
+
+    /// Payer CPF.
+    #[must_use]
+    pub fn cpf(mut self, value: Cpf) -> Self {
+        self.cpf = Some(value);
+        self
+    }
+
+    /// Payer CNPJ.
+    #[must_use]
+    pub fn cnpj(mut self, value: Cnpj) -> Self {
+        self.cnpj = Some(value);
+        self
+    }
+
+    #[must_use]
+    pub const fn pagina_atual(mut self, value: u32) -> Self {
+        self.pagina_atual = Some(value);
+        self
+    }
+
+    #[must_use]
+    pub const fn itens_por_pagina(mut self, value: u32) -> Self {
+        self.itens_por_pagina = Some(value);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PixRecebidoListResponse {
+    pub parametros: ListParametros,
+    pub pix: Vec<PixRecebido>,
 }
 
 // ========== Billing API - Split de Pagamento ==========
@@ -255,7 +985,8 @@ pub struct BillingRepasse {
     pub payee_code: String,
     /// transfer percentage, where 9000 equals 90%
     pub percentage: Option<i32>,
-    pub fixed: Option<i32>,
+    #[serde(default, with = "crate::money::cents::option")]
+    pub fixed: Option<Brl>,
 }
 
 #[derive(Debug, Clone, Serialize_repr, Deserialize_repr)]
@@ -282,7 +1013,8 @@ pub struct BillingMarketplaceResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BillingItem {
     pub name: String,
-    pub value: i64,
+    #[serde(with = "crate::money::cents")]
+    pub value: Brl,
     pub amount: i32,
     pub marketplace: Option<BillingMarketplace>,
 }
@@ -291,7 +1023,8 @@ pub struct BillingItem {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BillingItemResponse {
     pub name: String,
-    pub value: i64,
+    #[serde(with = "crate::money::cents")]
+    pub value: Brl,
     pub amount: i32,
     pub marketplace: Option<BillingMarketplaceResponse>,
 }
@@ -299,7 +1032,8 @@ pub struct BillingItemResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BillingShipping {
     pub name: String,
-    pub value: i64,
+    #[serde(with = "crate::money::cents")]
+    pub value: Brl,
 }
 
 #[skip_serializing_none]
@@ -317,14 +1051,14 @@ pub struct BillingAddress {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BillingJuridicalPerson {
     pub corporate_name: String,
-    pub cnpj: String,
+    pub cnpj: Cnpj,
 }
 
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BillingCustomer {
     pub name: Option<String>,
-    pub cpf: Option<String>,
+    pub cpf: Option<Cpf>,
     pub email: Option<String>,
     pub birth: Option<String>,
     pub phone_number: Option<String>,
@@ -332,6 +1066,46 @@ pub struct BillingCustomer {
     pub juridical_person: Option<BillingJuridicalPerson>,
 }
 
+/// [`BillingCustomer`] as returned on a charge. Documents are kept as sent,
+/// so an unexpected or masked one does not fail the whole charge.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillingCustomerResponse {
+    pub name: Option<String>,
+    pub cpf: Option<String>,
+    pub email: Option<String>,
+    pub birth: Option<String>,
+    pub phone_number: Option<String>,
+    pub address: Option<BillingAddress>,
+    pub juridical_person: Option<BillingJuridicalPersonResponse>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillingJuridicalPersonResponse {
+    pub corporate_name: Option<String>,
+    pub cnpj: Option<String>,
+}
+
+impl From<BillingCustomer> for BillingCustomerResponse {
+    fn from(customer: BillingCustomer) -> Self {
+        Self {
+            name: customer.name,
+            cpf: customer.cpf.map(|cpf| cpf.to_string()),
+            email: customer.email,
+            birth: customer.birth,
+            phone_number: customer.phone_number,
+            address: customer.address,
+            juridical_person: customer.juridical_person.map(|company| {
+                BillingJuridicalPersonResponse {
+                    corporate_name: Some(company.corporate_name),
+                    cnpj: Some(company.cnpj.to_string()),
+                }
+            }),
+        }
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BillingBilletConfigurations {
@@ -342,7 +1116,7 @@ pub struct BillingBilletConfigurations {
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BillingBankingBillet {
-    pub expire_at: Option<String>,
+    pub expire_at: Option<Date>,
     pub customer: BillingCustomer,
     pub configurations: Option<BillingBilletConfigurations>,
     pub message: Option<String>,
@@ -413,15 +1187,16 @@ pub struct BillingChargeDetailResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BillingChargeDetailData {
     pub charge_id: i64,
-    pub total: i64,
-    pub status: String,
+    #[serde(with = "crate::money::cents")]
+    pub total: Brl,
+    pub status: BillingChargeStatus,
     pub custom_id: Option<String>,
     pub created_at: String,
     pub notification_url: Option<String>,
     pub items: Vec<BillingItemResponse>,
     pub history: Vec<BillingChargeHistoryEntry>,
     pub shippings: Option<Vec<BillingChargeShipping>>,
-    pub customer: Option<BillingCustomer>,
+    pub customer: Option<BillingCustomerResponse>,
     pub payment: BillingChargeDetailPayment,
 }
 
@@ -436,7 +1211,8 @@ pub struct BillingChargeHistoryEntry {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BillingChargeShipping {
     pub name: String,
-    pub value: i64,
+    #[serde(with = "crate::money::cents")]
+    pub value: Brl,
     pub payee_code: Option<String>,
 }
 
@@ -458,7 +1234,7 @@ pub struct BillingChargeDetailBankingBillet {
     pub link: String,
     pub billet_link: String,
     pub pdf: BillingPdf,
-    pub expire_at: String,
+    pub expire_at: Date,
 }
 
 #[skip_serializing_none]
@@ -470,10 +1246,11 @@ pub struct BillingChargeData {
     pub link: String,
     pub billet_link: String,
     pub pdf: BillingPdf,
-    pub expire_at: String,
+    pub expire_at: Date,
     pub charge_id: i64,
-    pub status: String,
-    pub total: i64,
+    pub status: BillingChargeStatus,
+    #[serde(with = "crate::money::cents")]
+    pub total: Brl,
     pub payment: String,
 }
 
@@ -495,8 +1272,8 @@ pub struct BillingPdf {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CarnetCustomer {
     pub name: Option<String>,
-    pub cpf: Option<String>,
-    pub cnpj: Option<String>,
+    pub cpf: Option<Cpf>,
+    pub cnpj: Option<Cnpj>,
     pub email: Option<String>,
     pub phone_number: Option<String>,
     pub birth: Option<String>,
@@ -508,7 +1285,7 @@ pub struct CarnetCustomer {
 pub struct CarnetCreateRequest {
     pub items: Vec<BillingItem>,
     pub customer: CarnetCustomer,
-    pub expire_at: String,
+    pub expire_at: Date,
     pub message: Option<String>,
     pub repeats: i32,
     pub split_items: Option<bool>,
@@ -525,7 +1302,7 @@ pub struct CarnetMetadataRequest {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CarnetParcelRequest {
-    pub expire_at: String,
+    pub expire_at: Date,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -536,7 +1313,7 @@ pub struct CarnetParcelsRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CarnetParcel {
     pub parcel: i32,
-    pub expire_at: String,
+    pub expire_at: Date,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -548,12 +1325,17 @@ pub struct CarnetHistoryRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CarnetData {
     pub carnet_id: i64,
-    pub status: String,
+    pub status: CarnetStatus,
     pub cover: String,
     pub link: String,
     pub carnet_link: String,
     pub pdf: CarnetPdf,
     pub charges: Vec<CarnetCharge>,
+    pub custom_id: Option<String>,
+    pub notification_url: Option<String>,
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub history: Vec<BillingChargeHistoryEntry>,
 }
 
 #[skip_serializing_none]
@@ -568,9 +1350,10 @@ pub struct CarnetPdf {
 pub struct CarnetCharge {
     pub charge_id: i64,
     pub parcel: String,
-    pub status: String,
-    pub value: i64,
-    pub expire_at: String,
+    pub status: BillingChargeStatus,
+    #[serde(with = "crate::money::cents")]
+    pub value: Brl,
+    pub expire_at: Date,
     pub url: String,
     pub parcel_link: String,
     pub pdf: CarnetChargePdf,
@@ -607,8 +1390,9 @@ pub struct BillingNotificationData {
     pub status: BillingNotificationStatus,
     #[serde(rename = "type")]
     pub r#type: String,
-    pub received_by_bank_at: Option<String>,
-    pub value: Option<i64>,
+    pub received_by_bank_at: Option<Date>,
+    #[serde(default, with = "crate::money::cents::option")]
+    pub value: Option<Brl>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -618,6 +1402,46 @@ pub struct BillingNotificationIdentifiers {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BillingNotificationStatus {
-    pub current: String,
-    pub previous: Option<String>,
+    pub current: BillingChargeStatus,
+    pub previous: Option<BillingChargeStatus>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BillingCustomerResponse, CobResponse, CobvResponse};
+
+    #[test]
+    fn charge_responses_keep_masked_documents() {
+        let cob: CobResponse = serde_json::from_str(
+            r#"{"txid":"7978c0c97ea847e78e8849634473c1f1","devedor":{"cpf":"***.456.789-**","nome":"F"}}"#,
+        )
+        .unwrap();
+        let devedor = cob.devedor.unwrap();
+        assert_eq!(devedor.cpf.as_deref(), Some("***.456.789-**"));
+        assert_eq!(devedor.nome.as_deref(), Some("F"));
+
+        let cobv: CobvResponse = serde_json::from_str(
+            r#"{"txid":"7978c0c97ea847e78e8849634473c1f1","devedor":{"cnpj":"12.***.***/0001-**","nome":"Empresa","cidade":"Recife"}}"#,
+        )
+        .unwrap();
+        let devedor = cobv.devedor.unwrap();
+        assert_eq!(devedor.pessoa.cnpj.as_deref(), Some("12.***.***/0001-**"));
+        assert_eq!(devedor.cidade.as_deref(), Some("Recife"));
+    }
+
+    #[test]
+    fn billing_customer_responses_keep_masked_documents() {
+        let customer: BillingCustomerResponse = serde_json::from_str(
+            r#"{"name":"Gorbadoc Oldbuck","cpf":"945******06","juridical_person":{"corporate_name":"Fazenda","cnpj":"99.***.***/0001-**"}}"#,
+        )
+        .unwrap();
+        assert_eq!(customer.cpf.as_deref(), Some("945******06"));
+        assert_eq!(
+            customer
+                .juridical_person
+                .and_then(|company| company.cnpj)
+                .as_deref(),
+            Some("99.***.***/0001-**")
+        );
+    }
 }
@@ -0,0 +1,167 @@
+use efi_bank::simulator::Simulator;
+use efi_bank::{
+    BillingChargeCreateRequest, BillingChargeStatus, BillingItem, Brl, CarnetCreateRequest,
+    CarnetCustomer, CarnetStatus, Client, ClientBuilder, CobPayload, CobStatus, DevolucaoPayload,
+    ErrorKind, PixKey, PixTransactionPayload, Txid,
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+async fn start() -> (Simulator, Client) {
+    let sim = Simulator::start("id", "secret").await.unwrap();
+    let client = ClientBuilder::new()
+        .credentials("id", "secret")
+        .endpoints(sim.endpoints())
+        .build()
+        .unwrap();
+    (sim, client)
+}
+
+fn customer() -> CarnetCustomer {
+    CarnetCustomer {
+        name: Some("Gorbadoc Oldbuck".to_owned()),
+        cpf: Some("52998224725".parse().unwrap()),
+        cnpj: None,
+        email: None,
+        phone_number: None,
+        birth: None,
+        juridical_person: None,
+    }
+}
+
+#[tokio::test]
+async fn auth_rejects_wrong_credentials() {
+    let (sim, client) = start().await;
+    client.authenticate().await.unwrap();
+    client.authenticate_billing().await.unwrap();
+
+    let wrong = ClientBuilder::new()
+        .credentials("id", "wrong")
+        .endpoints(sim.endpoints())
+        .build()
+        .unwrap();
+    let error = wrong.authenticate().await.unwrap_err();
+    assert_eq!(error.kind(), Some(ErrorKind::Unauthorized));
+}
+
+#[tokio::test]
+async fn cob_is_paid_and_refunded() {
+    let (sim, client) = start().await;
+    let payload = CobPayload::builder(
+        PixKey::email("loja@example.com").unwrap(),
+        Brl::from_cents(10_000),
+    )
+    .build()
+    .unwrap();
+    let txid = Txid::from_seed("pedido-1");
+
+    let cob = client.cob_create_with_txid(&txid, &payload).await.unwrap();
+    assert_eq!(cob.status, Some(CobStatus::Ativa));
+    let error = client
+        .cob_create_with_txid(&txid, &payload)
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), Some(ErrorKind::Conflict));
+
+    sim.fake().pay_cob(txid.as_str()).unwrap();
+    let cob = client.cob_get(txid.as_str()).await.unwrap();
+    assert_eq!(cob.status, Some(CobStatus::Concluida));
+    let e2e_id = cob.pix.unwrap()[0].end_to_end_id.clone();
+
+    let refund = DevolucaoPayload::new(Brl::from_cents(6_000));
+    client
+        .pix_refund_create(&e2e_id, "devolucao1", &refund)
+        .await
+        .unwrap();
+    let got = client.pix_refund_get(&e2e_id, "devolucao1").await.unwrap();
+    assert_eq!(got.valor, Brl::from_cents(6_000));
+
+    let error = client
+        .pix_refund_create(&e2e_id, "devolucao2", &refund)
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), Some(ErrorKind::Validation));
+}
+
+#[tokio::test]
+async fn pix_send_is_idempotent_by_id_envio() {
+    let (sim, client) = start().await;
+    let payload = PixTransactionPayload::new(
+        Brl::from_cents(2_500),
+        PixKey::email("loja@example.com").unwrap(),
+        PixKey::email("fornecedor@example.com").unwrap().into(),
+    )
+    .idempotency_key("pagamento1");
+
+    let first = client.pix_send(&payload).await.unwrap();
+    let second = client.pix_send(&payload).await.unwrap();
+    assert_eq!(first.id_envio, "pagamento1");
+    assert_eq!(first.end_to_end_id, second.end_to_end_id);
+    assert_eq!(sim.fake().pix_transactions().len(), 1);
+
+    let sent = client
+        .pix_get_transaction_by_id_envio("pagamento1")
+        .await
+        .unwrap();
+    assert_eq!(sent.end_to_end_id, first.end_to_end_id);
+}
+
+#[tokio::test]
+async fn billing_charge_is_created_and_paid() {
+    let (sim, client) = start().await;
+    let request = BillingChargeCreateRequest::builder()
+        .item(BillingItem::new("Plano mensal", Brl::from_cents(4_990), 2))
+        .custom_id("pedido-42")
+        .build()
+        .unwrap();
+
+    let charge = client.billing_charge_create(&request).await.unwrap();
+    assert_eq!(charge.data.total, Brl::from_cents(9_980));
+
+    sim.fake()
+        .pay_billing_charge(charge.data.charge_id)
+        .unwrap();
+    let detail = client
+        .billing_charge_get(charge.data.charge_id)
+        .await
+        .unwrap();
+    assert_eq!(detail.data.status, BillingChargeStatus::Paid);
+
+    let error = client.billing_charge_get(999_999).await.unwrap_err();
+    assert_eq!(error.kind(), Some(ErrorKind::NotFound));
+}
+
+#[tokio::test]
+async fn carnet_is_created_and_cancelled() {
+    let (_sim, client) = start().await;
+    let request = CarnetCreateRequest::builder(customer(), "2030-01-31".parse().unwrap(), 3)
+        .item(BillingItem::new("Mensalidade", Brl::from_cents(10_000), 1))
+        .build()
+        .unwrap();
+
+    let carnet = client.carnet_create(&request).await.unwrap();
+    let expire_at: Vec<_> = carnet
+        .data
+        .charges
+        .iter()
+        .map(|charge| charge.expire_at.to_string())
+        .collect();
+    assert_eq!(expire_at, ["2030-01-31", "2030-02-28", "2030-03-31"]);
+
+    let cancelled = client.carnet_cancel(carnet.data.carnet_id).await.unwrap();
+    assert_eq!(cancelled.data.status, CarnetStatus::Canceled);
+}
+
+#[tokio::test]
+async fn oversized_body_is_rejected() {
+    let (sim, _client) = start().await;
+    let mut stream = TcpStream::connect(sim.addr()).await.unwrap();
+    stream
+        .write_all(b"POST /v2/cob HTTP/1.1\r\ncontent-length: 2097152\r\n\r\n")
+        .await
+        .unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 413"), "{response}");
+}